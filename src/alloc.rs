@@ -0,0 +1,194 @@
+#[cfg(any(test, feature = "alloc"))]
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  cell::Cell,
+};
+
+use crate::output::Record;
+
+// Whether `Counting` is the global allocator. It's opt in, with the `alloc`
+// feature, so that builds without it don't pay for counting every allocation.
+// Tests always have it.
+pub const INSTALLED: bool = cfg!(any(test, feature = "alloc"));
+
+// What the current thread allocated while inside `measure`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+  pub allocations: u64,
+  pub bytes: u64,
+  // most bytes that were live at once, counted from the start of the measurement
+  pub peak: u64,
+}
+
+impl Usage {
+  // Sums allocations and bytes, and keeps the larger of the two peaks.
+  pub fn combine(self, other: Usage) -> Usage {
+    Usage {
+      allocations: self.allocations + other.allocations,
+      bytes: self.bytes + other.bytes,
+      peak: self.peak.max(other.peak),
+    }
+  }
+
+  // The usage columns, null for a part that has no usage because it failed, so
+  // every record has the same columns whenever allocations are counted.
+  pub fn fields(usage: Option<&Usage>, record: Record) -> Record {
+    record
+      .field("allocations", usage.map(|usage| usage.allocations))
+      .field("allocated_bytes", usage.map(|usage| usage.bytes))
+      .field("peak_bytes", usage.map(|usage| usage.peak))
+  }
+}
+
+#[cfg(any(test, feature = "alloc"))]
+#[derive(Clone, Copy)]
+struct Counters {
+  enabled: bool,
+  allocations: u64,
+  bytes: u64,
+  // memory freed here may have been allocated before the measurement started
+  live: i64,
+  peak: i64,
+}
+
+#[cfg(any(test, feature = "alloc"))]
+impl Counters {
+  const IDLE: Counters = Counters {
+    enabled: false,
+    allocations: 0,
+    bytes: 0,
+    live: 0,
+    peak: 0,
+  };
+}
+
+// Counters are per thread, so parts running side by side don't see each other's
+// allocations. A `Cell` of plain data needs no destructor, which keeps it safe
+// to touch from inside the allocator.
+#[cfg(any(test, feature = "alloc"))]
+thread_local! {
+  static COUNTERS: Cell<Counters> = const { Cell::new(Counters::IDLE) };
+}
+
+#[cfg(any(test, feature = "alloc"))]
+fn update(f: impl FnOnce(&mut Counters)) {
+  // the thread local is gone while its thread is being torn down
+  let _ = COUNTERS.try_with(|cell| {
+    let mut counters = cell.get();
+    if counters.enabled {
+      f(&mut counters);
+      cell.set(counters);
+    }
+  });
+}
+
+#[cfg(any(test, feature = "alloc"))]
+fn allocated(size: usize) {
+  update(|counters| {
+    counters.allocations += 1;
+    counters.bytes += size as u64;
+    counters.live += size as i64;
+    counters.peak = counters.peak.max(counters.live);
+  });
+}
+
+#[cfg(any(test, feature = "alloc"))]
+fn freed(size: usize) {
+  update(|counters| counters.live -= size as i64);
+}
+
+// Wraps the system allocator and counts on threads that are inside `measure`,
+// every other allocation only pays for a thread local lookup.
+#[cfg(any(test, feature = "alloc"))]
+pub struct Counting;
+
+#[cfg(any(test, feature = "alloc"))]
+unsafe impl GlobalAlloc for Counting {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let ptr = System.alloc(layout);
+    if !ptr.is_null() {
+      allocated(layout.size());
+    }
+    ptr
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    let ptr = System.alloc_zeroed(layout);
+    if !ptr.is_null() {
+      allocated(layout.size());
+    }
+    ptr
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout);
+    freed(layout.size());
+  }
+
+  // A reallocation counts as a new allocation of the new size replacing the old one.
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    let new = System.realloc(ptr, layout, new_size);
+    if !new.is_null() {
+      freed(layout.size());
+      allocated(new_size);
+    }
+    new
+  }
+}
+
+// Runs `f` with allocation counting switched on for the current thread.
+// Measurements don't nest, and work handed off to other threads isn't counted.
+#[cfg(any(test, feature = "alloc"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+  COUNTERS.with(|cell| {
+    cell.set(Counters {
+      enabled: true,
+      ..Counters::IDLE
+    })
+  });
+
+  let result = f();
+
+  let counters = COUNTERS.with(|cell| cell.replace(Counters::IDLE));
+  let usage = Usage {
+    allocations: counters.allocations,
+    bytes: counters.bytes,
+    peak: counters.peak.max(0) as u64,
+  };
+
+  (result, usage)
+}
+
+// Without the allocator there's nothing to count, `--alloc` is refused before
+// it gets here.
+#[cfg(not(any(test, feature = "alloc")))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+  (f(), Usage::default())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_measure() {
+    let (kept, usage) = measure(|| {
+      let dropped = vec![0u8; 1000];
+      drop(dropped);
+      vec![0u8; 400]
+    });
+
+    assert_eq!(kept.len(), 400);
+    assert_eq!(
+      usage,
+      Usage {
+        allocations: 2,
+        bytes: 1400,
+        peak: 1000,
+      }
+    );
+
+    let (_, usage) = measure(|| 1 + 1);
+    assert_eq!(usage, Usage::default());
+  }
+}
@@ -0,0 +1,436 @@
+use std::{
+  collections::BTreeMap,
+  fs::{self, OpenOptions},
+  io::{self, ErrorKind, Write},
+  path::Path,
+};
+
+use crate::{
+  cli::Part,
+  output::Record,
+  runner::{self, Entry},
+  table::{Align, Table},
+};
+
+pub const PATH: &str = "answers.txt";
+const HEADER: &str = "\
+# Expected answers, one per line: <day> <part> <input hash> <answer>
+# The hash is an FNV-1a of the input as it is read, with any byte order mark
+# dropped and CRLF turned into LF, see `fs::read_bytes` and `fs::hash`.";
+
+#[derive(Debug, Default)]
+pub struct Answers {
+  expected: BTreeMap<(u8, Part, String), String>,
+}
+
+fn malformed(path: &Path, line: usize, message: String) -> io::Error {
+  io::Error::new(
+    ErrorKind::InvalidData,
+    format!("{}:{}: {}", path.display(), line, message),
+  )
+}
+
+impl Answers {
+  // A missing file is treated as an empty one, every answer is then unknown.
+  pub fn load(path: &Path) -> io::Result<Answers> {
+    let contents = match fs::read_to_string(path) {
+      Ok(contents) => contents,
+      Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Answers::default()),
+      Err(error) => return Err(error),
+    };
+
+    let mut answers = Answers::default();
+
+    for (index, line) in contents.lines().enumerate() {
+      // everything after a '#' is a comment, recorded answers use it to name the input file
+      let line = line.split('#').next().unwrap_or_default().trim();
+      if line.is_empty() {
+        continue;
+      }
+
+      match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [day, part, hash, answer] => {
+          let day = day
+            .parse::<u8>()
+            .map_err(|_| malformed(path, index + 1, format!("invalid day '{}'", day)))?;
+          let part = part
+            .parse::<Part>()
+            .map_err(|message| malformed(path, index + 1, message))?;
+          // the same answer twice is harmless, two different ones can't both be right
+          let key = (day, part, hash.to_string());
+          match answers.expected.get(&key) {
+            Some(existing) if existing != answer => {
+              return Err(malformed(
+                path,
+                index + 1,
+                format!(
+                  "day {} part {} is already expected to be {}",
+                  day, part, existing
+                ),
+              ))
+            }
+            _ => answers.expected.insert(key, answer.to_string()),
+          };
+        }
+        _ => {
+          return Err(malformed(
+            path,
+            index + 1,
+            "expected '<day> <part> <input hash> <answer>'".to_string(),
+          ))
+        }
+      }
+    }
+
+    Ok(answers)
+  }
+
+  pub fn insert(&mut self, day: u8, part: Part, hash: String, answer: String) {
+    self.expected.insert((day, part, hash), answer);
+  }
+
+  pub fn get(&self, day: u8, part: Part, hash: &str) -> Option<&str> {
+    self
+      .expected
+      .get(&(day, part, hash.to_string()))
+      .map(String::as_str)
+  }
+}
+
+// Appends the answers of every verification whose outcome is unknown, returning how many were added.
+pub fn record(path: &Path, verifications: &[Verification]) -> io::Result<usize> {
+  let unknown = verifications
+    .iter()
+    .filter(|verification| verification.outcome == Outcome::Unknown)
+    .filter_map(|Verification { entry, .. }| {
+      let hash = entry.input_hash.as_ref()?;
+      let answer = entry.answer.as_ref()?;
+      Some(format!(
+        "{} {} {} {} # {}",
+        entry.day, entry.part, hash, answer, entry.input
+      ))
+    })
+    .collect::<Vec<_>>();
+
+  if unknown.is_empty() {
+    return Ok(0);
+  }
+
+  let is_new = !path.exists();
+  let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+  if is_new {
+    writeln!(file, "{}", HEADER)?;
+  }
+
+  for line in unknown.iter() {
+    writeln!(file, "{}", line)?;
+  }
+
+  Ok(unknown.len())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+  Pass,
+  Fail { expected: String },
+  Unknown,
+  Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Verification {
+  pub entry: Entry,
+  pub outcome: Outcome,
+}
+
+pub fn verify(entries: Vec<Entry>, answers: &Answers) -> Vec<Verification> {
+  entries
+    .into_iter()
+    .map(|entry| {
+      let expected = entry
+        .input_hash
+        .as_ref()
+        .and_then(|hash| answers.get(entry.day, entry.part, hash));
+
+      let outcome = match (&entry.answer, expected) {
+        (None, _) => Outcome::Error,
+        (Some(_), None) => Outcome::Unknown,
+        (Some(answer), Some(expected)) if answer == expected => Outcome::Pass,
+        (Some(_), Some(expected)) => Outcome::Fail {
+          expected: expected.to_string(),
+        },
+      };
+
+      Verification { entry, outcome }
+    })
+    .collect()
+}
+
+impl Outcome {
+  pub fn label(&self) -> &'static str {
+    match self {
+      Outcome::Pass => "pass",
+      Outcome::Fail { .. } => "fail",
+      Outcome::Unknown => "unknown",
+      Outcome::Error => "error",
+    }
+  }
+}
+
+pub fn records(verifications: &[Verification]) -> Vec<Record> {
+  verifications
+    .iter()
+    .map(|Verification { entry, outcome }| {
+      let expected = match outcome {
+        Outcome::Pass => entry.answer.clone(),
+        Outcome::Fail { expected } => Some(expected.clone()),
+        Outcome::Unknown | Outcome::Error => None,
+      };
+
+      Record::new()
+        .field("day", entry.day)
+        .field("part", entry.part.to_string())
+        .field("input", entry.input.as_str())
+        .field("input_hash", entry.input_hash.clone())
+        .field("answer", entry.answer.clone())
+        .field("expected", expected)
+        .field("outcome", outcome.label())
+        .field("parse_ns", entry.parse)
+        .field("solve_ns", entry.solve)
+        .field("error", entry.status.message())
+    })
+    .collect()
+}
+
+pub fn report(verifications: &[Verification]) -> Table {
+  let inputs = runner::mixed_inputs(verifications.iter().map(|verification| &verification.entry));
+
+  let mut columns = vec![
+    ("day", Align::Right),
+    ("part", Align::Right),
+    ("answer", Align::Right),
+    ("expected", Align::Right),
+    ("status", Align::Left),
+  ];
+  if inputs {
+    columns.insert(1, ("input", Align::Left));
+  }
+  let mut table = Table::new(&columns);
+
+  for Verification { entry, outcome } in verifications {
+    let answer = entry.answer.clone().unwrap_or_else(|| "-".to_string());
+    let (expected, status) = match outcome {
+      Outcome::Pass => (answer.clone(), "pass".to_string()),
+      Outcome::Fail { expected } => (expected.clone(), "FAIL".to_string()),
+      Outcome::Unknown => ("?".to_string(), "unknown".to_string()),
+      Outcome::Error => ("-".to_string(), entry.status.to_string()),
+    };
+
+    let mut cells = vec![
+      entry.day.to_string(),
+      entry.part.to_string(),
+      answer,
+      expected,
+      status,
+    ];
+    if inputs {
+      cells.insert(1, entry.input.clone());
+    }
+    table.row(cells);
+  }
+
+  let count = |f: fn(&Outcome) -> bool| {
+    verifications
+      .iter()
+      .filter(|verification| f(&verification.outcome))
+      .count()
+  };
+
+  let mut cells = vec![
+    "total".to_string(),
+    verifications.len().to_string(),
+    String::new(),
+    String::new(),
+    format!(
+      "{} passed, {} failed, {} unknown, {} errors",
+      count(|outcome| *outcome == Outcome::Pass),
+      count(|outcome| matches!(outcome, Outcome::Fail { .. })),
+      count(|outcome| *outcome == Outcome::Unknown),
+      count(|outcome| *outcome == Outcome::Error),
+    ),
+  ];
+  if inputs {
+    cells.insert(1, String::new());
+  }
+
+  table.separator();
+  table.row(cells);
+
+  table
+}
+
+pub fn all_passed(verifications: &[Verification]) -> bool {
+  verifications
+    .iter()
+    .all(|verification| matches!(verification.outcome, Outcome::Pass | Outcome::Unknown))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::runner::Status;
+  use std::{env, path::PathBuf};
+
+  fn file(name: &str, contents: Option<&str>) -> PathBuf {
+    let path = env::temp_dir().join(format!("aoc-answers-{}-{}.txt", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    if let Some(contents) = contents {
+      fs::write(&path, contents).unwrap();
+    }
+    path
+  }
+
+  // Day 7 on the input whose answers the tests expect.
+  fn hashed(part: Part, answer: Option<&str>, status: Status) -> Entry {
+    Entry {
+      input_hash: Some("04c92f93f3639d8f".to_string()),
+      ..runner::entry(7, part, answer, status)
+    }
+  }
+
+  fn outcomes(verifications: &[Verification]) -> Vec<Outcome> {
+    verifications
+      .iter()
+      .map(|verification| verification.outcome.clone())
+      .collect()
+  }
+
+  #[test]
+  fn test_load() {
+    let path = file(
+      "load",
+      Some(
+        "\
+# a comment
+7 1 04c92f93f3639d8f 3749 # day_07.txt
+
+7 2 04c92f93f3639d8f 11387
+7 2 04c92f93f3639d8f 11387
+7 1 somethingelse 42
+",
+      ),
+    );
+
+    let answers = Answers::load(&path).unwrap();
+    assert_eq!(answers.get(7, Part::One, "04c92f93f3639d8f"), Some("3749"));
+    assert_eq!(answers.get(7, Part::Two, "04c92f93f3639d8f"), Some("11387"));
+    assert_eq!(answers.get(7, Part::One, "somethingelse"), Some("42"));
+    assert_eq!(answers.get(8, Part::One, "04c92f93f3639d8f"), None);
+
+    fs::remove_file(&path).unwrap();
+    assert!(Answers::load(&path).unwrap().expected.is_empty());
+  }
+
+  #[test]
+  fn test_malformed() {
+    let error = |name: &str, contents: &str| {
+      let path = file(name, Some(contents));
+      let message = Answers::load(&path).unwrap_err().to_string();
+      fs::remove_file(&path).unwrap();
+      message.replace(&path.display().to_string(), "answers.txt")
+    };
+
+    assert_eq!(
+      error("fields", "# header\n7 1 04c92f93f3639d8f\n"),
+      "answers.txt:2: expected '<day> <part> <input hash> <answer>'"
+    );
+    assert_eq!(
+      error("day", "seven 1 04c92f93f3639d8f 3749\n"),
+      "answers.txt:1: invalid day 'seven'"
+    );
+    assert!(error("part", "7 3 04c92f93f3639d8f 3749\n").starts_with("answers.txt:1: "));
+    assert_eq!(
+      error(
+        "duplicate",
+        "7 1 04c92f93f3639d8f 3749\n7 1 04c92f93f3639d8f 3750\n"
+      ),
+      "answers.txt:2: day 7 part 1 is already expected to be 3749"
+    );
+  }
+
+  #[test]
+  fn test_verify() {
+    let mut answers = Answers::default();
+    answers.insert(
+      7,
+      Part::One,
+      "04c92f93f3639d8f".to_string(),
+      "3749".to_string(),
+    );
+
+    let entries = vec![
+      hashed(Part::One, Some("3749"), Status::Ok),
+      hashed(Part::Two, Some("11387"), Status::Ok),
+    ];
+    let verifications = verify(entries, &answers);
+    assert_eq!(
+      outcomes(&verifications),
+      vec![Outcome::Pass, Outcome::Unknown]
+    );
+    assert!(all_passed(&verifications));
+
+    let entries = vec![
+      hashed(Part::One, Some("3750"), Status::Ok),
+      hashed(Part::Two, None, Status::Error("not solved".to_string())),
+    ];
+    let verifications = verify(entries, &answers);
+    assert_eq!(
+      outcomes(&verifications),
+      vec![
+        Outcome::Fail {
+          expected: "3749".to_string()
+        },
+        Outcome::Error
+      ]
+    );
+    assert!(!all_passed(&verifications[..1]));
+    assert!(!all_passed(&verifications[1..]));
+  }
+
+  #[test]
+  fn test_record() {
+    let path = file("record", None);
+    let mut answers = Answers::default();
+    answers.insert(
+      7,
+      Part::One,
+      "04c92f93f3639d8f".to_string(),
+      "3749".to_string(),
+    );
+
+    let entries = vec![
+      hashed(Part::One, Some("3749"), Status::Ok),
+      hashed(Part::Two, Some("11387"), Status::Ok),
+    ];
+    assert_eq!(
+      record(&path, &verify(entries.clone(), &answers)).unwrap(),
+      1
+    );
+    assert_eq!(
+      fs::read_to_string(&path).unwrap(),
+      format!("{}\n7 2 04c92f93f3639d8f 11387 # day_07.txt\n", HEADER)
+    );
+
+    // once recorded, part 2 is known and nothing new is written
+    let answers = Answers::load(&path).unwrap();
+    assert_eq!(
+      outcomes(&verify(entries.clone(), &answers)),
+      vec![Outcome::Unknown, Outcome::Pass]
+    );
+    let entries = vec![hashed(Part::Two, Some("11387"), Status::Ok)];
+    assert_eq!(record(&path, &verify(entries, &answers)).unwrap(), 0);
+
+    fs::remove_file(&path).unwrap();
+  }
+}
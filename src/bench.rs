@@ -0,0 +1,243 @@
+use std::{
+  fmt::{self, Display},
+  hint::black_box,
+  io,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  alloc::{self, Usage},
+  cli::Part,
+  fs::{self, Source},
+  output::Record,
+  solution::DynSolution,
+  stats::Stats,
+  table::{self, Align, Table},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+  pub warmup: usize,
+  pub iterations: usize,
+  // count allocations in one extra run after sampling, so timings are unaffected
+  pub alloc: bool,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      warmup: 2,
+      iterations: 10,
+      alloc: false,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+  Parse,
+  Solve(Part),
+}
+
+impl Display for Stage {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Stage::Parse => write!(f, "parse"),
+      Stage::Solve(part) => write!(f, "{}", part),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+  pub day: u8,
+  pub input: String,
+  pub stage: Stage,
+  pub stats: Result<Stats, String>,
+  pub memory: Option<Usage>,
+}
+
+fn timed<T>(run: impl FnOnce() -> io::Result<T>) -> io::Result<Duration> {
+  let start = Instant::now();
+  let result = run();
+  let elapsed = start.elapsed();
+  black_box(result?);
+  Ok(elapsed)
+}
+
+fn counted<T>(run: impl FnOnce() -> io::Result<T>) -> Option<Usage> {
+  let (result, usage) = alloc::measure(run);
+  result.ok().map(|_| usage)
+}
+
+fn sample(
+  options: &Options,
+  mut run: impl FnMut() -> io::Result<Duration>,
+) -> Result<Stats, String> {
+  for _ in 0..options.warmup {
+    run().map_err(|error| error.to_string())?;
+  }
+
+  let samples = (0..options.iterations)
+    .map(|_| run())
+    .collect::<io::Result<Vec<_>>>()
+    .map_err(|error| error.to_string())?;
+
+  Stats::new(&samples).ok_or_else(|| "no samples".to_string())
+}
+
+pub fn bench_day(
+  solution: &dyn DynSolution,
+  source: &Source,
+  parts: &[Part],
+  options: &Options,
+) -> Vec<Measurement> {
+  let day = solution.day();
+  let input = source.name();
+
+  // read the file once up front, so that only parsing is measured and not file I/O
+  let contents = match source.read() {
+    Ok(contents) => contents,
+    Err(error) => {
+      return vec![Measurement {
+        day,
+        input,
+        stage: Stage::Parse,
+        stats: Err(error.to_string()),
+        memory: None,
+      }];
+    }
+  };
+
+  let mut measurements = vec![Measurement {
+    day,
+    input: input.clone(),
+    stage: Stage::Parse,
+    stats: sample(options, || timed(|| solution.parse(&contents))),
+    memory: options
+      .alloc
+      .then(|| counted(|| solution.parse(&contents)))
+      .flatten(),
+  }];
+
+  let parsed = match solution.parse(&contents) {
+    Ok(parsed) => parsed,
+    Err(_) => return measurements,
+  };
+
+  for &part in parts {
+    measurements.push(Measurement {
+      day,
+      input: input.clone(),
+      stage: Stage::Solve(part),
+      stats: sample(options, || timed(|| solution.solve(part, &parsed))),
+      memory: options
+        .alloc
+        .then(|| counted(|| solution.solve(part, &parsed)))
+        .flatten(),
+    });
+  }
+
+  measurements
+}
+
+pub fn report(measurements: &[Measurement]) -> Table {
+  let memory = measurements
+    .iter()
+    .any(|measurement| measurement.memory.is_some());
+  let inputs = measurements
+    .iter()
+    .any(|measurement| !fs::is_default_input(measurement.day, &measurement.input));
+
+  let mut columns = vec![
+    ("day", Align::Right),
+    ("part", Align::Right),
+    ("min", Align::Right),
+    ("median", Align::Right),
+    ("mean", Align::Right),
+    ("p95", Align::Right),
+    ("stddev", Align::Right),
+    ("outliers", Align::Right),
+  ];
+  if memory {
+    columns.extend(table::MEMORY_COLUMNS);
+  }
+  columns.push(("status", Align::Left));
+  if inputs {
+    columns.insert(1, ("input", Align::Left));
+  }
+  let mut table = Table::new(&columns);
+
+  for measurement in measurements {
+    let mut cells = vec![measurement.day.to_string(), measurement.stage.to_string()];
+    match &measurement.stats {
+      Ok(stats) => {
+        cells.extend(
+          [stats.min, stats.median, stats.mean, stats.p95, stats.stddev]
+            .into_iter()
+            .map(table::duration),
+        );
+        cells.push(format!("{}/{}", stats.outliers, stats.samples));
+      }
+      Err(_) => cells.extend((0..6).map(|_| "-".to_string())),
+    }
+    if memory {
+      cells.extend(table::memory(measurement.memory));
+    }
+    match &measurement.stats {
+      Ok(_) => cells.push("ok".to_string()),
+      Err(message) => cells.push(format!("error: {}", message)),
+    }
+    if inputs {
+      cells.insert(1, measurement.input.clone());
+    }
+    table.row(cells);
+  }
+
+  let median_total = measurements
+    .iter()
+    .filter_map(|measurement| measurement.stats.as_ref().ok())
+    .map(|stats| stats.median)
+    .sum::<Duration>();
+
+  let mut cells = vec![
+    "total".to_string(),
+    String::new(),
+    String::new(),
+    table::duration(median_total),
+  ];
+  if inputs {
+    cells.insert(1, String::new());
+  }
+
+  table.separator();
+  table.row(cells);
+
+  table
+}
+
+pub fn records(measurements: &[Measurement], alloc: bool) -> Vec<Record> {
+  measurements
+    .iter()
+    .map(|measurement| {
+      let stats = measurement.stats.as_ref().ok();
+      let record = Record::new()
+        .field("day", measurement.day)
+        .field("part", measurement.stage.to_string())
+        .field("input", measurement.input.as_str())
+        .field("samples", stats.map(|stats| stats.samples))
+        .field("min_ns", stats.map(|stats| stats.min))
+        .field("median_ns", stats.map(|stats| stats.median))
+        .field("mean_ns", stats.map(|stats| stats.mean))
+        .field("p95_ns", stats.map(|stats| stats.p95))
+        .field("stddev_ns", stats.map(|stats| stats.stddev))
+        .field("outliers", stats.map(|stats| stats.outliers));
+      let record = if alloc {
+        Usage::fields(measurement.memory.as_ref(), record)
+      } else {
+        record
+      };
+      record.field("error", measurement.stats.as_ref().err().cloned())
+    })
+    .collect()
+}
@@ -0,0 +1,794 @@
+use std::{
+  collections::BTreeMap,
+  fmt::{self, Display},
+  path::PathBuf,
+  str::FromStr,
+  time::Duration,
+};
+
+use crate::{
+  bench,
+  fs::{self, Source},
+  history,
+  output::Format,
+  runner, site, watch,
+};
+
+pub const USAGE: &str = "\
+usage: aoc <command> [options]
+
+commands:
+  run <days>...      run the selected days
+  bench <days>...    benchmark parsing and solving the selected days
+  compare            compare the latest benchmark against earlier ones
+  verify <days>...   check the selected days against answers.txt
+  examples <days>... check the selected days against their puzzle examples
+  watch <day>        re-run a day whenever its input changes
+  fetch <days>...    download inputs that aren't in input/ yet
+  submit <day> <part>
+                     run a part and send its answer to the puzzle site
+  leaderboard <path> show a private leaderboard exported as JSON
+  new <day>          add a module for a day and register it
+
+days:
+  7                  a single day
+  1..13              an inclusive range of days
+  1,3,5              a list of days
+  all                every implemented day
+
+options:
+  -p, --part <1|2>   only run the given part
+  --warmup <n>       untimed runs before sampling (bench, default 2)
+  -n, --iterations <n>
+                     timed runs per part (bench, default 10)
+  -j, --jobs <n>     threads to spread days and parts over (run, verify)
+  --serial           run one part at a time, for accurate timings (run, verify)
+  --timeout <secs>   give up on a parse or part after this long, 0 waits
+                     forever (run, verify, default 60)
+  --alloc            count allocations, bytes and peak memory per part
+                     (run, bench, needs a build with --features alloc)
+  --input [<day>=]<path>
+                     read a day's input from this file instead, - for stdin
+  --all-inputs       also run each day on every input/day_NN/*.txt
+                     (run, verify, bench)
+  --warn-whitespace  warn about input lines with trailing whitespace
+  --also <path>      also run against this file, may be repeated (watch)
+  --record           save answers that are not known yet (verify)
+  --url <url>        http:// address of the puzzle site or a proxy for it
+                     (fetch, submit, default $AOC_URL or
+                     http://adventofcode.com)
+  --threshold <pct>  slowdown flagged as a regression (compare, default 10%)
+  --baseline <n>     earlier runs to compare against (compare, default 5)
+  --format <fmt>     table, json or csv (run, verify, bench, leaderboard)
+  -h, --help         print this message";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub fn all() -> Vec<Part> {
+    vec![Part::One, Part::Two]
+  }
+}
+
+impl Display for Part {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Part::One => write!(f, "1"),
+      Part::Two => write!(f, "2"),
+    }
+  }
+}
+
+impl FromStr for Part {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "1" => Ok(Part::One),
+      "2" => Ok(Part::Two),
+      other => Err(format!("invalid part '{}'", other)),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Days {
+  All,
+  Only(Vec<u8>),
+}
+
+impl Days {
+  pub fn resolve(&self, available: &[u8]) -> Result<Vec<u8>, String> {
+    match self {
+      Days::All => Ok(available.to_vec()),
+      Days::Only(days) => {
+        for day in days {
+          if !available.contains(day) {
+            return Err(format!("day {} is not implemented", day));
+          }
+        }
+        Ok(days.clone())
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+  pub days: Days,
+  pub parts: Vec<Part>,
+  pub format: Format,
+  // days read from somewhere other than their usual input file
+  pub inputs: BTreeMap<u8, Source>,
+  // also run every day against the files in its input set
+  pub all_inputs: bool,
+  // point out input lines that end in whitespace
+  pub warn_whitespace: bool,
+}
+
+impl Selection {
+  pub fn source(&self, day: u8) -> Source {
+    self
+      .inputs
+      .get(&day)
+      .cloned()
+      .unwrap_or_else(|| Source::day(day))
+  }
+
+  // The inputs a day runs against, with `--all-inputs` its usual one followed by
+  // its input set. A missing usual file is skipped when the set has others.
+  pub fn sources(&self, day: u8) -> Result<Vec<Source>, String> {
+    let source = self.source(day);
+    if !self.all_inputs {
+      return Ok(vec![source]);
+    }
+
+    let set = fs::input_set(day)
+      .map_err(|error| format!("could not list {}: {}", fs::day_dir(day).display(), error))?;
+
+    let missing = matches!(&source, Source::Path(path) if !path.exists());
+    let mut sources = Vec::new();
+    if !missing || set.is_empty() {
+      sources.push(source);
+    }
+    sources.extend(set.into_iter().map(Source::Path));
+
+    Ok(sources)
+  }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifyOptions {
+  pub record: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+  Run(Selection, runner::Options),
+  Bench(Selection, bench::Options),
+  Compare(history::Options),
+  Verify(Selection, runner::Options, VerifyOptions),
+  Examples(Selection, runner::Options),
+  Watch(Selection, runner::Options, watch::Options),
+  Fetch(Days, site::Options),
+  Submit(u8, Part, runner::Options, site::Options),
+  Leaderboard(PathBuf, Format),
+  New(u8),
+  Help,
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+  match s.trim().parse::<u8>() {
+    Ok(day) if (1..=25).contains(&day) => Ok(day),
+    _ => Err(format!("invalid day '{}'", s)),
+  }
+}
+
+fn parse_days(arg: &str, days: &mut Vec<u8>) -> Result<(), String> {
+  for item in arg.split(',').filter(|item| !item.is_empty()) {
+    // ranges are inclusive on both ends, so `1..13` covers the whole calendar so far
+    let range = item.split_once("..=").or_else(|| item.split_once(".."));
+
+    match range {
+      Some((from, to)) => {
+        let (from, to) = (parse_day(from)?, parse_day(to)?);
+        if from > to {
+          return Err(format!("empty day range '{}'", item));
+        }
+        days.extend(from..=to);
+      }
+      None => days.push(parse_day(item)?),
+    }
+  }
+
+  Ok(())
+}
+
+fn parse_part(s: Option<String>) -> Result<Part, String> {
+  match s {
+    Some(s) => s.parse(),
+    None => Err("expected a part after --part".to_string()),
+  }
+}
+
+fn parse_count(flag: &str, s: Option<String>) -> Result<usize, String> {
+  match s.as_deref().map(str::parse::<usize>) {
+    Some(Ok(count)) => Ok(count),
+    Some(Err(_)) => Err(format!("invalid count '{}' for {}", s.unwrap(), flag)),
+    None => Err(format!("expected a count after {}", flag)),
+  }
+}
+
+fn parse_percent(flag: &str, s: Option<String>) -> Result<f64, String> {
+  let Some(s) = s else {
+    return Err(format!("expected a percentage after {}", flag));
+  };
+
+  match s.trim_end_matches('%').parse::<f64>() {
+    Ok(percent) if percent >= 0.0 => Ok(percent),
+    _ => Err(format!("invalid percentage '{}' for {}", s, flag)),
+  }
+}
+
+fn parse_timeout(flag: &str, s: Option<String>) -> Result<Option<Duration>, String> {
+  let Some(s) = s else {
+    return Err(format!("expected seconds after {}", flag));
+  };
+
+  match s.parse::<f64>() {
+    Ok(0.0) => Ok(None),
+    Ok(secs) if secs > 0.0 && secs.is_finite() => Ok(Some(Duration::from_secs_f64(secs))),
+    _ => Err(format!("invalid timeout '{}' for {}", s, flag)),
+  }
+}
+
+fn parse_runner_option(
+  options: &mut runner::Options,
+  flag: &str,
+  args: &mut dyn Iterator<Item = String>,
+) -> Result<bool, String> {
+  match flag {
+    "-j" | "--jobs" => match parse_count(flag, args.next())? {
+      0 => return Err(format!("{} must be at least 1", flag)),
+      jobs => options.jobs = jobs,
+    },
+    "--serial" => options.jobs = 1,
+    "--timeout" => options.timeout = parse_timeout(flag, args.next())?,
+    _ => return Ok(false),
+  }
+  Ok(true)
+}
+
+// `<day>=<path>` overrides one day, a bare path is for the only selected day.
+fn parse_input(s: &str) -> Result<(Option<u8>, Source), String> {
+  match s.split_once('=') {
+    Some((day, path)) if !day.is_empty() && day.chars().all(|c| c.is_ascii_digit()) => {
+      Ok((Some(parse_day(day)?), Source::parse(path)))
+    }
+    _ => Ok((None, Source::parse(s))),
+  }
+}
+
+fn resolve_inputs(
+  days: &Days,
+  inputs: Vec<(Option<u8>, Source)>,
+) -> Result<BTreeMap<u8, Source>, String> {
+  let mut resolved = BTreeMap::new();
+
+  for (day, source) in inputs {
+    let day = match (day, days) {
+      (Some(day), _) => day,
+      (None, Days::Only(days)) if days.len() == 1 => days[0],
+      (None, _) => {
+        return Err(format!(
+          "--input {} needs a single day, use --input <day>={}",
+          source, source
+        ))
+      }
+    };
+
+    if let Days::Only(days) = days {
+      if !days.contains(&day) {
+        return Err(format!(
+          "--input given for day {}, which is not selected",
+          day
+        ));
+      }
+    }
+    if resolved.insert(day, source).is_some() {
+      return Err(format!("more than one --input for day {}", day));
+    }
+  }
+
+  Ok(resolved)
+}
+
+// Parses the day and part selection shared by all commands. Any other flag is
+// offered to `option`, which returns whether it recognised the flag.
+fn parse_selection(
+  args: impl Iterator<Item = String>,
+  mut option: impl FnMut(&str, &mut dyn Iterator<Item = String>) -> Result<bool, String>,
+) -> Result<Selection, String> {
+  let mut all = false;
+  let mut days = Vec::new();
+  let mut parts = Vec::new();
+  let mut format = Format::default();
+  let mut inputs = Vec::new();
+  let mut all_inputs = false;
+  let mut warn_whitespace = false;
+  let mut args = args;
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "-p" | "--part" => parts.push(parse_part(args.next())?),
+      "--format" => match args.next() {
+        Some(s) => format = s.parse()?,
+        None => return Err("expected a format after --format".to_string()),
+      },
+      "--input" => match args.next() {
+        Some(s) => inputs.push(parse_input(&s)?),
+        None => return Err("expected a path after --input".to_string()),
+      },
+      "--all-inputs" => all_inputs = true,
+      "--warn-whitespace" => warn_whitespace = true,
+      "all" => all = true,
+      flag if flag.starts_with('-') => {
+        if !option(flag, &mut args)? {
+          return Err(format!("unknown option '{}'", flag));
+        }
+      }
+      _ => parse_days(&arg, &mut days)?,
+    }
+  }
+
+  let days = if all {
+    Days::All
+  } else if days.is_empty() {
+    return Err("expected at least one day".to_string());
+  } else {
+    days.sort();
+    days.dedup();
+    Days::Only(days)
+  };
+
+  if parts.is_empty() {
+    parts = Part::all();
+  }
+  parts.sort();
+  parts.dedup();
+
+  let inputs = resolve_inputs(&days, inputs)?;
+
+  Ok(Selection {
+    days,
+    parts,
+    format,
+    inputs,
+    all_inputs,
+    warn_whitespace,
+  })
+}
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+  let mut args = args.into_iter();
+
+  match args.next().as_deref() {
+    None | Some("-h") | Some("--help") | Some("help") => Ok(Command::Help),
+    Some("run") => {
+      let mut options = runner::Options::default();
+      let selection = parse_selection(args, |flag, args| {
+        match flag {
+          "--alloc" => options.alloc = true,
+          _ => return parse_runner_option(&mut options, flag, args),
+        }
+        Ok(true)
+      })?;
+      Ok(Command::Run(selection, options))
+    }
+    Some("verify") => {
+      let mut runner_options = runner::Options::default();
+      let mut options = VerifyOptions::default();
+      let selection = parse_selection(args, |flag, args| {
+        match flag {
+          "--record" => options.record = true,
+          _ => return parse_runner_option(&mut runner_options, flag, args),
+        }
+        Ok(true)
+      })?;
+      Ok(Command::Verify(selection, runner_options, options))
+    }
+    Some("examples") => {
+      let mut options = runner::Options::default();
+      let selection = parse_selection(args, |flag, args| {
+        parse_runner_option(&mut options, flag, args)
+      })?;
+      if !selection.inputs.is_empty() || selection.all_inputs {
+        return Err("examples don't read input files".to_string());
+      }
+      Ok(Command::Examples(selection, options))
+    }
+    Some("watch") => {
+      let mut runner_options = runner::Options::default();
+      let mut options = watch::Options::default();
+      let selection = parse_selection(args, |flag, args| {
+        match flag {
+          "--also" => match args.next() {
+            Some(path) => options.also.push(path.into()),
+            None => return Err("expected a path after --also".to_string()),
+          },
+          _ => return parse_runner_option(&mut runner_options, flag, args),
+        }
+        Ok(true)
+      })?;
+      match &selection.days {
+        Days::Only(days) if days.len() == 1 => {}
+        _ => return Err("watch takes a single day".to_string()),
+      }
+      if selection.format != Format::Table {
+        return Err("watch only prints tables".to_string());
+      }
+      if selection.all_inputs {
+        return Err("watch runs a single input, use --also".to_string());
+      }
+      Ok(Command::Watch(selection, runner_options, options))
+    }
+    Some("bench") => {
+      let mut options = bench::Options::default();
+      let selection = parse_selection(args, |flag, args| {
+        match flag {
+          "--warmup" => options.warmup = parse_count(flag, args.next())?,
+          "-n" | "--iterations" => options.iterations = parse_count(flag, args.next())?,
+          "--alloc" => options.alloc = true,
+          _ => return Ok(false),
+        }
+        Ok(true)
+      })?;
+      Ok(Command::Bench(selection, options))
+    }
+    Some("fetch") => {
+      let mut all = false;
+      let mut days = Vec::new();
+      let mut options = site::Options::default();
+      while let Some(arg) = args.next() {
+        match arg.as_str() {
+          "--url" => match args.next() {
+            Some(url) => options.url = Some(url),
+            None => return Err("expected a url after --url".to_string()),
+          },
+          "all" => all = true,
+          flag if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+          _ => parse_days(&arg, &mut days)?,
+        }
+      }
+
+      let days = if all {
+        Days::All
+      } else if days.is_empty() {
+        return Err("expected at least one day".to_string());
+      } else {
+        days.sort();
+        days.dedup();
+        Days::Only(days)
+      };
+      Ok(Command::Fetch(days, options))
+    }
+    Some("submit") => {
+      let mut day = None;
+      let mut part = None;
+      let mut runner_options = runner::Options::default();
+      let mut options = site::Options::default();
+      while let Some(arg) = args.next() {
+        match arg.as_str() {
+          "--url" => match args.next() {
+            Some(url) => options.url = Some(url),
+            None => return Err("expected a url after --url".to_string()),
+          },
+          flag if flag.starts_with('-') => {
+            if !parse_runner_option(&mut runner_options, flag, &mut args)? {
+              return Err(format!("unknown option '{}'", flag));
+            }
+          }
+          _ if day.is_none() => day = Some(parse_day(&arg)?),
+          _ if part.is_none() => part = Some(arg.parse()?),
+          _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+      }
+
+      match (day, part) {
+        (Some(day), Some(part)) => Ok(Command::Submit(day, part, runner_options, options)),
+        _ => Err("submit takes a day and a part".to_string()),
+      }
+    }
+    Some("new") => match (args.next(), args.next()) {
+      (Some(day), None) => Ok(Command::New(parse_day(&day)?)),
+      _ => Err("new takes a single day".to_string()),
+    },
+    Some("leaderboard") => {
+      let mut path = None;
+      let mut format = Format::default();
+      while let Some(arg) = args.next() {
+        match arg.as_str() {
+          "--format" => match args.next() {
+            Some(s) => format = s.parse()?,
+            None => return Err("expected a format after --format".to_string()),
+          },
+          flag if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+          _ if path.is_none() => path = Some(PathBuf::from(arg)),
+          _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+      }
+
+      match path {
+        Some(path) => Ok(Command::Leaderboard(path, format)),
+        None => Err("expected the path of an exported leaderboard".to_string()),
+      }
+    }
+    Some("compare") => {
+      let mut options = history::Options::default();
+      while let Some(flag) = args.next() {
+        match flag.as_str() {
+          "--threshold" => options.threshold = parse_percent(&flag, args.next())?,
+          "--baseline" => options.baseline = parse_count(&flag, args.next())?,
+          _ => return Err(format!("unknown option '{}'", flag)),
+        }
+      }
+      Ok(Command::Compare(options))
+    }
+    Some(other) => Err(format!("unknown command '{}'", other)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(|s| s.to_string()).collect()
+  }
+
+  #[test]
+  fn test_single_day_and_part() {
+    let expected = Command::Run(
+      Selection {
+        days: Days::Only(vec![7]),
+        parts: vec![Part::Two],
+        format: Format::Table,
+        inputs: BTreeMap::new(),
+        all_inputs: false,
+        warn_whitespace: false,
+      },
+      runner::Options::default(),
+    );
+    assert_eq!(parse(args("run 7 --part 2")), Ok(expected));
+  }
+
+  #[test]
+  fn test_ranges_and_lists() {
+    let expected = Command::Run(
+      Selection {
+        days: Days::Only(vec![1, 2, 3, 5, 9]),
+        parts: vec![Part::One, Part::Two],
+        format: Format::Table,
+        inputs: BTreeMap::new(),
+        all_inputs: false,
+        warn_whitespace: false,
+      },
+      runner::Options::default(),
+    );
+    assert_eq!(parse(args("run 1..3 9,5 2..=2")), Ok(expected));
+  }
+
+  #[test]
+  fn test_all() {
+    let expected = Command::Run(
+      Selection {
+        days: Days::All,
+        parts: vec![Part::One],
+        format: Format::Json,
+        inputs: BTreeMap::new(),
+        all_inputs: false,
+        warn_whitespace: false,
+      },
+      runner::Options::default(),
+    );
+    assert_eq!(parse(args("run all -p 1 --format json")), Ok(expected));
+  }
+
+  #[test]
+  fn test_bench_options() {
+    let expected = Command::Bench(
+      Selection {
+        days: Days::Only(vec![6, 9]),
+        parts: vec![Part::One, Part::Two],
+        format: Format::Csv,
+        inputs: BTreeMap::new(),
+        all_inputs: false,
+        warn_whitespace: false,
+      },
+      bench::Options {
+        warmup: 0,
+        iterations: 50,
+        alloc: true,
+      },
+    );
+    assert_eq!(
+      parse(args("bench 6 9 --warmup 0 -n 50 --alloc --format csv")),
+      Ok(expected)
+    );
+    assert!(parse(args("run 6 --warmup 0")).is_err());
+    assert!(parse(args("verify 6 --alloc")).is_err());
+  }
+
+  #[test]
+  fn test_inputs() {
+    let inputs = |s: &str| {
+      parse(args(s)).map(|command| match command {
+        Command::Run(selection, _) => selection.inputs,
+        _ => unreachable!(),
+      })
+    };
+
+    assert_eq!(
+      inputs("run 7 --input stress.txt"),
+      Ok(BTreeMap::from([(7, Source::Path("stress.txt".into()))]))
+    );
+    assert_eq!(
+      inputs("run 6,7 --input 6=- --input 7=other/day_07.txt"),
+      Ok(BTreeMap::from([
+        (6, Source::Stdin),
+        (7, Source::Path("other/day_07.txt".into()))
+      ]))
+    );
+    assert!(inputs("run 6,7 --input stress.txt").is_err());
+    assert!(inputs("run 6 --input 7=stress.txt").is_err());
+    assert!(inputs("run 7 --input - --input 7=stress.txt").is_err());
+    assert!(parse(args("examples 7 --input stress.txt")).is_err());
+    assert!(parse(args("watch 7 --all-inputs")).is_err());
+
+    match parse(args("verify all --all-inputs")) {
+      Ok(Command::Verify(selection, _, _)) => assert!(selection.all_inputs),
+      other => panic!("unexpected {:?}", other),
+    }
+    match parse(args("run 7 --warn-whitespace")) {
+      Ok(Command::Run(selection, _)) => assert!(selection.warn_whitespace),
+      other => panic!("unexpected {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_watch_options() {
+    let expected = Command::Watch(
+      Selection {
+        days: Days::Only(vec![7]),
+        parts: vec![Part::Two],
+        format: Format::Table,
+        inputs: BTreeMap::new(),
+        all_inputs: false,
+        warn_whitespace: false,
+      },
+      runner::Options::default(),
+      watch::Options {
+        also: vec!["example.txt".into()],
+      },
+    );
+    assert_eq!(parse(args("watch 7 -p 2 --also example.txt")), Ok(expected));
+    assert!(parse(args("watch 6,7")).is_err());
+    assert!(parse(args("watch all")).is_err());
+    assert!(parse(args("watch 7 --format json")).is_err());
+  }
+
+  #[test]
+  fn test_fetch_options() {
+    let expected = Command::Fetch(
+      Days::Only(vec![14, 15]),
+      site::Options {
+        url: Some("http://127.0.0.1:8080".to_string()),
+      },
+    );
+    assert_eq!(
+      parse(args("fetch 15 14 --url http://127.0.0.1:8080")),
+      Ok(expected)
+    );
+    assert!(parse(args("fetch")).is_err());
+    assert!(parse(args("fetch 7 -p 1")).is_err());
+  }
+
+  #[test]
+  fn test_submit_options() {
+    let expected = Command::Submit(
+      7,
+      Part::Two,
+      runner::Options {
+        timeout: None,
+        ..runner::Options::default()
+      },
+      site::Options {
+        url: Some("http://127.0.0.1:8080".to_string()),
+      },
+    );
+    assert_eq!(
+      parse(args("submit 7 2 --timeout 0 --url http://127.0.0.1:8080")),
+      Ok(expected)
+    );
+    assert!(parse(args("submit 7")).is_err());
+    assert!(parse(args("submit 7 3")).is_err());
+    assert!(parse(args("submit 7 1 2")).is_err());
+    assert!(parse(args("submit 1..3 1")).is_err());
+  }
+
+  #[test]
+  fn test_leaderboard_options() {
+    assert_eq!(
+      parse(args("leaderboard board.json --format csv")),
+      Ok(Command::Leaderboard("board.json".into(), Format::Csv))
+    );
+    assert!(parse(args("leaderboard")).is_err());
+    assert!(parse(args("leaderboard a.json b.json")).is_err());
+  }
+
+  #[test]
+  fn test_new() {
+    assert_eq!(parse(args("new 14")), Ok(Command::New(14)));
+    assert!(parse(args("new")).is_err());
+    assert!(parse(args("new 26")).is_err());
+    assert!(parse(args("new 14 15")).is_err());
+  }
+
+  #[test]
+  fn test_compare_options() {
+    let expected = Command::Compare(history::Options {
+      threshold: 25.0,
+      baseline: 3,
+    });
+    assert_eq!(
+      parse(args("compare --threshold 25% --baseline 3")),
+      Ok(expected)
+    );
+    assert!(parse(args("compare --threshold fast")).is_err());
+  }
+
+  #[test]
+  fn test_runner_options() {
+    let jobs = |s: &str| match parse(args(s)) {
+      Ok(Command::Run(_, options)) | Ok(Command::Verify(_, options, _)) => Some(options.jobs),
+      _ => None,
+    };
+    assert_eq!(jobs("run all -j 3"), Some(3));
+    assert_eq!(jobs("verify all --jobs 3 --serial"), Some(1));
+    assert_eq!(jobs("run all --jobs 0"), None);
+
+    let timeout = |s: &str| match parse(args(s)) {
+      Ok(Command::Run(_, options)) => Ok(options.timeout),
+      Ok(_) => unreachable!(),
+      Err(message) => Err(message),
+    };
+    assert_eq!(
+      timeout("run 6 --timeout 2.5"),
+      Ok(Some(Duration::from_millis(2500)))
+    );
+    assert_eq!(timeout("run 6 --timeout 0"), Ok(None));
+    assert!(timeout("run 6 --timeout -1").is_err());
+  }
+
+  #[test]
+  fn test_errors() {
+    assert!(parse(args("run")).is_err());
+    assert!(parse(args("run 0")).is_err());
+    assert!(parse(args("run 5..2")).is_err());
+    assert!(parse(args("run 1 --part 3")).is_err());
+    assert!(parse(args("walk 1")).is_err());
+    assert!(parse(args("run 1 --format xml")).is_err());
+  }
+
+  #[test]
+  fn test_resolve() {
+    let available = [1, 2, 3];
+    assert_eq!(Days::All.resolve(&available), Ok(vec![1, 2, 3]));
+    assert_eq!(Days::Only(vec![2]).resolve(&available), Ok(vec![2]));
+    assert!(Days::Only(vec![4]).resolve(&available).is_err());
+  }
+}
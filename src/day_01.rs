@@ -1,33 +1,26 @@
-use std::{collections::HashMap, io, result};
-
-use crate::fs::read_day;
-
-fn parse_number_pairs(lines: impl Iterator<Item = String>) -> (Vec<u32>, Vec<u32>) {
-  lines
-    .map(|line| {
-      line
-        .split(" ")
-        .map(|str| str.trim())
-        .filter_map(|str| {
-          if str.is_empty() {
-            None
-          } else {
-            str.parse::<u32>().ok()
-          }
-        })
-        .collect::<Vec<_>>()
-    })
-    .filter_map(|vec| match vec.as_slice() {
-      [a, b] => Some((*a, *b)),
-      _ => None,
-    })
-    .unzip()
-}
+use std::{collections::HashMap, io};
+
+use crate::{
+  parse::{self, literal, pair, terminated, unsigned, whitespace},
+  solution::{Example, Solution},
+};
+
+// `3   4`, a number from each list with spaces between them.
+fn parse_number_pairs(input: &str) -> io::Result<(Vec<u32>, Vec<u32>)> {
+  let pairs: Vec<(u32, u32)> = parse::lines(
+    input,
+    pair(
+      terminated(unsigned(), pair(literal(" "), whitespace())),
+      unsigned(),
+    ),
+  )?;
 
-pub fn part_1() -> io::Result<u32> {
-  let lines = read_day(1)?;
+  Ok(pairs.into_iter().unzip())
+}
 
-  let (mut first, mut second) = parse_number_pairs(lines);
+fn part_1((first, second): &(Vec<u32>, Vec<u32>)) -> io::Result<u32> {
+  let mut first = first.clone();
+  let mut second = second.clone();
 
   first.sort();
   second.sort();
@@ -35,17 +28,13 @@ pub fn part_1() -> io::Result<u32> {
   let result = first
     .iter()
     .zip(second.iter())
-    .map(|(a, b)| if a > b { a - b } else { b - a })
+    .map(|(a, b)| a.abs_diff(*b))
     .sum::<u32>();
 
   Ok(result)
 }
 
-pub fn part_2() -> io::Result<u32> {
-  let lines = read_day(1)?;
-
-  let (first, second) = parse_number_pairs(lines);
-
+fn part_2((first, second): &(Vec<u32>, Vec<u32>)) -> io::Result<u32> {
   let second_frequency = second
     .iter()
     .fold(HashMap::<u32, u32>::new(), |mut acc, &num| {
@@ -63,3 +52,63 @@ pub fn part_2() -> io::Result<u32> {
 
   Ok(result)
 }
+
+const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+pub struct Day01;
+
+impl Solution for Day01 {
+  const DAY: u8 = 1;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("11"),
+    part_2: Some("31"),
+  }];
+
+  type Input = (Vec<u32>, Vec<u32>);
+  type Output = u32;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse_number_pairs(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<u32> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<u32> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse_number_pairs(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 11);
+    assert_eq!(part_2(&input).unwrap(), 31);
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    let error = |input: &str| parse_number_pairs(input).unwrap_err().to_string();
+    assert_eq!(
+      error("3   4\n4   x\n"),
+      "line 2, column 5: expected a number"
+    );
+    assert_eq!(
+      error("3   4 5\n"),
+      "line 1, column 6: expected the end of the line"
+    );
+    assert_eq!(error("34\n"), "line 1, column 3: expected ' '");
+  }
+}
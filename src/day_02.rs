@@ -1,7 +1,10 @@
-use crate::fs::read_day;
+use crate::{
+  parse::{self, literal, map, pair, separated, unsigned, whitespace},
+  solution::{Example, Solution},
+};
 use std::{fmt::Debug, io, num::ParseIntError, str::FromStr};
 
-struct Report {
+pub struct Report {
   levels: Vec<Level>,
 }
 
@@ -65,7 +68,7 @@ impl Report {
       let abs_diff = diff.abs();
 
       // If the diff is not okay then either the left or right has to be removed
-      if abs_diff < 1 || abs_diff > 3 {
+      if !(1..=3).contains(&abs_diff) {
         consider_removing(&self.levels, index);
         consider_removing(&self.levels, index + 1);
         break;
@@ -103,23 +106,80 @@ impl FromStr for Level {
   }
 }
 
-fn read_input() -> io::Result<impl Iterator<Item = Report>> {
-  let result = read_day(2)?
-    .map(|line| {
-      line
-        .split(" ")
-        .filter_map(|str| str.trim().parse::<Level>().ok())
-        .collect::<Vec<Level>>()
-    })
-    .map(|levels| Report { levels });
-
-  Ok(result)
+// `7 6 4 2 1`, the levels of one report.
+fn parse(input: &str) -> io::Result<Vec<Report>> {
+  parse::lines(
+    input,
+    map(
+      separated(unsigned::<Level>(), pair(literal(" "), whitespace())),
+      |levels| Report { levels },
+    ),
+  )
 }
 
-pub fn part_1() -> io::Result<usize> {
-  Ok(read_input()?.filter(|report| report.is_safe()).count())
+fn part_1(reports: &[Report]) -> io::Result<usize> {
+  Ok(reports.iter().filter(|report| report.is_safe()).count())
 }
 
-pub fn part_2() -> io::Result<usize> {
-  Ok(read_input()?.filter(|report| report.dampened()).count())
+fn part_2(reports: &[Report]) -> io::Result<usize> {
+  Ok(reports.iter().filter(|report| report.dampened()).count())
+}
+
+const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+pub struct Day02;
+
+impl Solution for Day02 {
+  const DAY: u8 = 2;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("2"),
+    part_2: Some("4"),
+  }];
+
+  type Input = Vec<Report>;
+  type Output = usize;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<usize> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<usize> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 2);
+    assert_eq!(part_2(&input).unwrap(), 4);
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    let error = |input: &str| parse(input).unwrap_err().to_string();
+    assert_eq!(
+      error("7 6 4\n1 2 -7\n"),
+      "line 2, column 5: expected a number"
+    );
+    assert_eq!(
+      error("7 6,4\n"),
+      "line 1, column 4: expected the end of the line"
+    );
+  }
 }
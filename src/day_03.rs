@@ -1,4 +1,6 @@
-use std::{fs, io};
+use std::io;
+
+use crate::solution::{Example, Solution};
 
 #[derive(Debug, Clone, Copy)]
 struct Invocation {
@@ -51,25 +53,21 @@ impl ParseState {
   }
 }
 
-fn starts_with(input: &[char], cursor: usize, value: &str) -> bool {
-  let read = input
-    .iter()
-    .skip(cursor)
-    .take(value.len())
-    .collect::<String>();
-
-  read == value
+fn starts_with(input: &[u8], cursor: usize, value: &str) -> bool {
+  input
+    .get(cursor..)
+    .is_some_and(|rest| rest.starts_with(value.as_bytes()))
 }
 
-fn read_number(input: &[char], cursor: usize, max_width: usize) -> Option<(u32, usize)> {
+fn read_number(input: &[u8], cursor: usize, max_width: usize) -> Option<(u32, usize)> {
   let mut width = 0;
   let mut value = 0;
   let new_cursor = cursor;
 
   while width < max_width {
     let c = input.get(new_cursor + width)?;
-    if c.is_digit(10) {
-      value = value * 10 + c.to_digit(10)?;
+    if c.is_ascii_digit() {
+      value = value * 10 + (c - b'0') as u32;
       width += 1;
     } else {
       break;
@@ -79,10 +77,8 @@ fn read_number(input: &[char], cursor: usize, max_width: usize) -> Option<(u32,
   Some((value, new_cursor + width))
 }
 
-pub fn part_1() -> io::Result<u32> {
-  let input = fs::read_to_string("input/day_03.txt")?;
-  let input = input.chars().collect::<Vec<_>>();
-  let input = input.as_slice();
+// Every `mul` in the memory, ignoring `do()` and `don't()`.
+fn scan_all(input: &[u8]) -> Vec<Invocation> {
   let length = input.len();
   let mut state = ParseState::Running(vec![]);
   let mut cursor = 0;
@@ -93,7 +89,7 @@ pub fn part_1() -> io::Result<u32> {
         if starts_with(input, cursor, "mul(") {
           state = ParseState::Invoke {
             backtrack_to: cursor,
-            accumulated: accumulated,
+            accumulated,
           };
           cursor += 4;
         } else {
@@ -177,19 +173,11 @@ pub fn part_1() -> io::Result<u32> {
     }
   }
 
-  Ok(
-    state
-      .accumulated()
-      .iter()
-      .map(|invocation| invocation.apply())
-      .sum(),
-  )
+  state.accumulated().clone()
 }
 
-pub fn part_2() -> io::Result<u32> {
-  let input = fs::read_to_string("input/day_03.txt")?;
-  let input = input.chars().collect::<Vec<_>>();
-  let input = input.as_slice();
+// The `mul`s that aren't switched off by a `don't()`.
+fn scan_enabled(input: &[u8]) -> Vec<Invocation> {
   let length = input.len();
   let mut state = ParseState::Running(vec![]);
   let mut cursor = 0;
@@ -204,7 +192,7 @@ pub fn part_2() -> io::Result<u32> {
         if starts_with(input, cursor, "mul(") {
           state = ParseState::Invoke {
             backtrack_to: cursor,
-            accumulated: accumulated,
+            accumulated,
           };
           cursor += 4;
         } else {
@@ -294,11 +282,86 @@ pub fn part_2() -> io::Result<u32> {
     }
   }
 
-  Ok(
-    state
-      .accumulated()
-      .iter()
-      .map(|invocation| invocation.apply())
-      .sum(),
-  )
+  state.accumulated().clone()
+}
+
+#[derive(Debug)]
+pub struct Memory {
+  all: Vec<Invocation>,
+  enabled: Vec<Invocation>,
+}
+
+// The memory is scanned byte by byte straight from the input's buffer, `&[u8]`
+// indexes without decoding anything. Only the invocations found are kept.
+fn parse(input: &str) -> io::Result<Memory> {
+  let input = input.as_bytes();
+  Ok(Memory {
+    all: scan_all(input),
+    enabled: scan_enabled(input),
+  })
+}
+
+fn sum(invocations: &[Invocation]) -> u32 {
+  invocations
+    .iter()
+    .map(|invocation| invocation.apply())
+    .sum()
+}
+
+fn part_1(memory: &Memory) -> io::Result<u32> {
+  Ok(sum(&memory.all))
+}
+
+fn part_2(memory: &Memory) -> io::Result<u32> {
+  Ok(sum(&memory.enabled))
+}
+
+const EXAMPLE_1: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+const EXAMPLE_2: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+pub struct Day03;
+
+impl Solution for Day03 {
+  const DAY: u8 = 3;
+  const EXAMPLES: &'static [Example] = &[
+    Example {
+      input: EXAMPLE_1,
+      part_1: Some("161"),
+      part_2: None,
+    },
+    Example {
+      input: EXAMPLE_2,
+      part_1: None,
+      part_2: Some("48"),
+    },
+  ];
+
+  type Input = Memory;
+  type Output = u32;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<u32> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<u32> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE_1).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 161);
+
+    let input = parse(EXAMPLE_2).unwrap();
+    assert_eq!(part_2(&input).unwrap(), 48);
+  }
 }
@@ -1,6 +1,9 @@
 use std::io;
 
-use crate::fs::read_day;
+use crate::{
+  fs,
+  solution::{Example, Solution},
+};
 
 #[derive(Debug, Clone, Copy)]
 enum Direction {
@@ -85,29 +88,29 @@ impl Direction {
   }
 }
 
-struct Grid {
-  grid: Vec<Vec<char>>,
+pub struct Grid {
+  grid: fs::Grid,
 }
 
 impl Grid {
-  fn unsafe_get(&self, x: usize, y: usize) -> char {
-    self.grid[y][x]
+  fn unsafe_get(&self, x: usize, y: usize) -> u8 {
+    self.grid[(x, y)]
   }
 
-  fn get(&self, x: usize, y: usize) -> Option<char> {
-    self.grid.get(y).and_then(|row| row.get(x).copied())
+  fn get(&self, x: usize, y: usize) -> Option<u8> {
+    self.grid.get(x, y)
   }
 
   fn in_bounds(&self, x: usize, y: usize) -> bool {
-    x < self.width() && y < self.height()
+    self.grid.in_bounds(x, y)
   }
 
   fn width(&self) -> usize {
-    self.grid.get(0).map_or(0, |row| row.len())
+    self.grid.width()
   }
 
   fn height(&self) -> usize {
-    self.grid.len()
+    self.grid.height()
   }
 
   fn contains_texts_around(
@@ -122,8 +125,8 @@ impl Grid {
       return false;
     }
 
-    let aa = format!("{}{}", self.unsafe_get(x, y), a);
-    let bb = format!("{}{}", self.unsafe_get(x, y), b);
+    let aa = format!("{}{}", self.unsafe_get(x, y) as char, a);
+    let bb = format!("{}{}", self.unsafe_get(x, y) as char, b);
 
     (self.contains_text(x, y, &aa, direction)
       && self.contains_text(x, y, &bb, &direction.opposite()))
@@ -144,31 +147,27 @@ impl Grid {
       None
     };
 
-    text.into().chars().all(|c| match position {
-      Some((x, y)) => {
-        if self.get(x, y) == Some(c) {
-          position = direction.move_position(x, y);
-          true
-        } else {
-          false
-        }
+    text.into().bytes().all(|c| match position {
+      Some((x, y)) if self.get(x, y) == Some(c) => {
+        position = direction.move_position(x, y);
+        true
       }
-      None => false,
+      _ => false,
     })
   }
 }
 
-pub fn part_1() -> io::Result<u32> {
-  let grid = Grid {
-    grid: read_day(4)?
-      .map(|line| line.chars().collect::<Vec<_>>())
-      .collect::<Vec<_>>(),
-  };
+fn parse(input: &str) -> io::Result<Grid> {
+  Ok(Grid {
+    grid: fs::Grid::parse(input.as_bytes())?,
+  })
+}
 
+fn part_1(grid: &Grid) -> io::Result<u32> {
   let result = (0..grid.width())
     .flat_map(|x| (0..grid.height()).map(move |y| (x, y)))
     .filter_map(|(x, y)| {
-      if grid.unsafe_get(x, y) == 'X' {
+      if grid.unsafe_get(x, y) == b'X' {
         Some(
           Direction::all()
             .iter()
@@ -190,17 +189,11 @@ pub fn part_1() -> io::Result<u32> {
   Ok(result)
 }
 
-pub fn part_2() -> io::Result<u32> {
-  let grid = Grid {
-    grid: read_day(4)?
-      .map(|line| line.chars().collect::<Vec<_>>())
-      .collect::<Vec<_>>(),
-  };
-
+fn part_2(grid: &Grid) -> io::Result<u32> {
   let result = (0..grid.width())
     .flat_map(|x| (0..grid.height()).map(move |y| (x, y)))
     .filter_map(|(x, y)| {
-      if grid.unsafe_get(x, y) == 'A' {
+      if grid.unsafe_get(x, y) == b'A' {
         let ul = grid.contains_texts_around(x, y, "M", "S", &Direction::UpLeft);
         let ur = grid.contains_texts_around(x, y, "M", "S", &Direction::UpRight);
         if ul && ur {
@@ -216,3 +209,53 @@ pub fn part_2() -> io::Result<u32> {
 
   Ok(result)
 }
+
+const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+pub struct Day04;
+
+impl Solution for Day04 {
+  const DAY: u8 = 4;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("18"),
+    part_2: Some("9"),
+  }];
+
+  type Input = Grid;
+  type Output = u32;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<u32> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<u32> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 18);
+    assert_eq!(part_2(&input).unwrap(), 9);
+  }
+}
@@ -1,10 +1,13 @@
 use std::{
   collections::{HashMap, HashSet},
   io,
-  str::FromStr,
 };
 
-use crate::fs::read_day;
+use crate::{
+  fs,
+  parse::{self, literal, map, pair, separated, terminated, unsigned},
+  solution::{Example, Solution},
+};
 
 #[derive(Debug, Clone, Copy)]
 struct Rule {
@@ -19,62 +22,45 @@ struct Update {
 }
 
 #[derive(Debug)]
-struct Input {
-  rules: Vec<Rule>,
+pub struct Input {
+  graph: DirectedGraph,
   updates: Vec<Update>,
 }
 
-fn input() -> io::Result<Input> {
-  let lines = read_day(5)?
-    .map(|line| line.trim().to_string())
-    .collect::<Vec<_>>();
-
-  let divider_index = lines.iter().position(|line| line.is_empty()).unwrap();
-
-  let (rules, updates) = lines.split_at(divider_index);
-  let updates = &updates[1..];
-
-  let rules = rules
-    .iter()
-    .map(|line| {
-      let line = line
-        .split("|")
-        .filter_map(|s| s.trim().parse::<u32>().ok())
-        .collect::<Vec<_>>();
-
-      let slice = line.as_slice();
-
-      match slice {
-        [before, after] => Some(Rule {
-          before: *before,
-          after: *after,
-        }),
-        _ => None,
-      }
-    })
-    .collect::<Vec<_>>();
-
-  let updates = updates
-    .iter()
-    .map(|line| {
-      let sequence = line
-        .split(",")
-        .filter_map(|s| s.trim().parse::<u32>().ok())
-        .collect::<Vec<_>>();
-
-      Update { sequence }
-    })
-    .collect::<Vec<_>>();
+fn parse(input: &str) -> io::Result<Input> {
+  let [rules, updates] = fs::sections(input).collect::<Vec<_>>()[..] else {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      "expected the rules and the updates separated by a blank line",
+    ));
+  };
+
+  // `47|53`
+  let rules = parse::each(
+    rules.lines(),
+    map(
+      pair(terminated(unsigned(), literal("|")), unsigned()),
+      |(before, after)| Rule { before, after },
+    ),
+  )?;
+
+  // `75,47,61,53,29`
+  let updates = parse::each(
+    updates.lines(),
+    map(separated(unsigned(), literal(",")), |sequence| Update {
+      sequence,
+    }),
+  )?;
 
   Ok(Input {
-    rules: rules.into_iter().flatten().collect(),
-    updates: updates,
+    graph: make_topsort_graph(&rules),
+    updates,
   })
 }
 
 type DirectedGraph = HashMap<u32, HashSet<u32>>;
 
-fn make_topsort_graph(rules: Vec<Rule>) -> DirectedGraph {
+fn make_topsort_graph(rules: &[Rule]) -> DirectedGraph {
   let mut graph = DirectedGraph::new();
 
   for rule in rules {
@@ -85,8 +71,8 @@ fn make_topsort_graph(rules: Vec<Rule>) -> DirectedGraph {
   graph
 }
 
-fn get_middle_element(v: &Vec<u32>) -> Option<u32> {
-  if v.len() % 2 == 0 {
+fn get_middle_element(v: &[u32]) -> Option<u32> {
+  if v.len().is_multiple_of(2) {
     None
   } else {
     Some(v[v.len() / 2])
@@ -101,9 +87,8 @@ fn match_rules(graph: &DirectedGraph, update: &Update) -> bool {
       return false;
     }
 
-    match graph.get(num) {
-      Some(set) => not_allowed.extend(set.iter()),
-      None => {}
+    if let Some(set) = graph.get(num) {
+      not_allowed.extend(set.iter());
     }
   }
 
@@ -130,31 +115,95 @@ fn fix_update(update: &Update, graph: &DirectedGraph) -> Update {
   Update { sequence: sorted }
 }
 
-pub fn part_1() -> io::Result<u32> {
-  let Input { rules, updates } = input()?;
-  let graph = make_topsort_graph(rules);
+fn part_1(input: &Input) -> io::Result<u32> {
+  let Input { graph, updates } = input;
 
   let result = updates
     .iter()
-    .filter(|update| match_rules(&graph, update))
+    .filter(|update| match_rules(graph, update))
     .filter_map(|update| get_middle_element(&update.sequence))
     .sum();
 
   Ok(result)
 }
 
-pub fn part_2() -> io::Result<u32> {
-  let Input { rules, updates } = input()?;
-  let graph = make_topsort_graph(rules);
-
-  println!("{:?}", graph);
+fn part_2(input: &Input) -> io::Result<u32> {
+  let Input { graph, updates } = input;
 
   let result = updates
     .iter()
-    .filter(|update| !match_rules(&graph, update))
-    .map(|update| fix_update(update, &graph))
+    .filter(|update| !match_rules(graph, update))
+    .map(|update| fix_update(update, graph))
     .filter_map(|update| get_middle_element(&update.sequence))
     .sum();
 
   Ok(result)
 }
+
+const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+pub struct Day05;
+
+impl Solution for Day05 {
+  const DAY: u8 = 5;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("143"),
+    part_2: Some("123"),
+  }];
+
+  type Input = Input;
+  type Output = u32;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<u32> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<u32> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 143);
+    assert_eq!(part_2(&input).unwrap(), 123);
+  }
+}
@@ -3,7 +3,7 @@ use std::{
   io,
 };
 
-use crate::fs::read_day;
+use crate::solution::{Example, Solution};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 enum Direction {
@@ -90,7 +90,7 @@ struct Transform {
   direction: Direction,
 }
 
-struct Input {
+pub struct Input {
   grid: Grid,
   guard: Transform,
 }
@@ -150,8 +150,9 @@ fn sim_guard(input: &Input) -> Outcome {
   }
 }
 
-fn input() -> io::Result<Input> {
-  let lines = read_day(6)?
+fn parse(input: &str) -> io::Result<Input> {
+  let lines = input
+    .lines()
     .map(|line| line.trim().chars().collect::<Vec<_>>())
     .collect::<Vec<_>>();
   let height = lines.len();
@@ -204,21 +205,15 @@ fn input() -> io::Result<Input> {
   })
 }
 
-pub fn part_1() -> io::Result<usize> {
-  let input = input()?;
-
-  match sim_guard(&input) {
-    Outcome::Loops { .. } => {
-      io::Result::Err(io::Error::new(io::ErrorKind::Other, "guard is in a loop"))
-    }
+fn part_1(input: &Input) -> io::Result<usize> {
+  match sim_guard(input) {
+    Outcome::Loops { .. } => io::Result::Err(io::Error::other("guard is in a loop")),
     Outcome::Escapes { visited } => Ok(visited.len()),
   }
 }
 
-pub fn part_2() -> io::Result<usize> {
-  let input = input()?;
-
-  let mut reachable_by_guard = match sim_guard(&input) {
+fn part_2(input: &Input) -> io::Result<usize> {
+  let mut reachable_by_guard = match sim_guard(input) {
     Outcome::Loops { visited } => visited.keys().cloned().collect::<HashSet<_>>(),
     Outcome::Escapes { visited } => visited.keys().cloned().collect::<HashSet<_>>(),
   };
@@ -230,7 +225,7 @@ pub fn part_2() -> io::Result<usize> {
     .map(|position| {
       sim_guard(&Input {
         grid: input.grid.obstruct(&position),
-        guard: input.guard.clone(),
+        guard: input.guard,
       })
     })
     .fold(0, |acc, outcome| match outcome {
@@ -240,3 +235,53 @@ pub fn part_2() -> io::Result<usize> {
 
   Ok(result)
 }
+
+const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+pub struct Day06;
+
+impl Solution for Day06 {
+  const DAY: u8 = 6;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("41"),
+    part_2: Some("6"),
+  }];
+
+  type Input = Input;
+  type Output = usize;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<usize> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<usize> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 41);
+    assert_eq!(part_2(&input).unwrap(), 6);
+  }
+}
@@ -1,28 +1,18 @@
-use std::{io, num::ParseIntError};
-
-use crate::fs::read_day;
-
-fn input() -> io::Result<Vec<(u64, Vec<u64>)>> {
-  Ok(
-    read_day(7)?
-      .map(|line| {
-        let parts = line.split(":").collect::<Vec<_>>();
-        let parts = parts.as_slice();
-        match parts {
-          [before, after] => {
-            let left = before.trim().parse::<u64>().expect("expected number");
-            let right = after
-              .trim()
-              .split(" ")
-              .map(|s| s.parse::<u64>())
-              .collect::<Result<Vec<_>, ParseIntError>>()
-              .expect("expected numbers");
-            (left, right)
-          }
-          _ => panic!("unexpected line"),
-        }
-      })
-      .collect::<Vec<_>>(),
+use std::io;
+
+use crate::{
+  parse::{self, literal, pair, separated, terminated, unsigned, whitespace},
+  solution::{Example, Solution},
+};
+
+// `190: 10 19`, the test value and the numbers to combine into it.
+fn parse(input: &str) -> io::Result<Vec<(u64, Vec<u64>)>> {
+  parse::lines(
+    input,
+    pair(
+      terminated(unsigned(), pair(literal(":"), whitespace())),
+      separated(unsigned(), literal(" ")),
+    ),
   )
 }
 
@@ -97,7 +87,7 @@ fn evals_to(components: &[u64], ops: &[BinOp], mut target: u64) -> bool {
         }
       }
       BinOp::Mult => {
-        if target % current == 0 {
+        if target.is_multiple_of(current) {
           target /= current;
           if cursor == 1 {
             return target == components[0];
@@ -131,8 +121,7 @@ fn evals_to(components: &[u64], ops: &[BinOp], mut target: u64) -> bool {
   }
 }
 
-pub fn part_1() -> io::Result<u64> {
-  let input = input()?;
+fn part_1(input: &[(u64, Vec<u64>)]) -> io::Result<u64> {
   let result = input
     .iter()
     .flat_map(|(total, components)| {
@@ -146,8 +135,7 @@ pub fn part_1() -> io::Result<u64> {
   Ok(result)
 }
 
-pub fn part_2() -> io::Result<u64> {
-  let input = input()?;
+fn part_2(input: &[(u64, Vec<u64>)]) -> io::Result<u64> {
   let result = input
     .iter()
     .flat_map(|(total, components)| {
@@ -161,9 +149,58 @@ pub fn part_2() -> io::Result<u64> {
   Ok(result)
 }
 
+const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+pub struct Day07;
+
+impl Solution for Day07 {
+  const DAY: u8 = 7;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("3749"),
+    part_2: Some("11387"),
+  }];
+
+  type Input = Vec<(u64, Vec<u64>)>;
+  type Output = u64;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<u64> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<u64> {
+    part_2(input)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::fs::Source;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 3749);
+    assert_eq!(part_2(&input).unwrap(), 11387);
+  }
+
+  fn input() -> Vec<(u64, Vec<u64>)> {
+    parse(&Source::day(7).read().unwrap()).unwrap()
+  }
 
   #[test]
   fn test_permutations_part_1() {
@@ -194,11 +231,14 @@ mod tests {
 
   #[test]
   fn test_part_1() {
-    assert_eq!(part_1().unwrap(), 10741443549536);
+    assert_eq!(part_1(&input()).unwrap(), 10741443549536);
   }
 
+  // slow, run with cargo test -- --ignored
+  #[test]
+  #[ignore]
   fn test_part_2() {
-    assert_eq!(part_2().unwrap(), 500335179214836);
+    assert_eq!(part_2(&input()).unwrap(), 500335179214836);
   }
 
   #[test]
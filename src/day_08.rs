@@ -3,7 +3,7 @@ use std::{
   io,
 };
 
-use crate::fs::read_day;
+use crate::solution::{Example, Solution};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Position(usize, usize);
@@ -23,94 +23,51 @@ impl Position {
 
 type Out = usize;
 
-struct In {
+pub struct In {
   grid: Grid,
   antennas: HashMap<char, Vec<Position>>,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Cell {
-  Empty,
-  Antenna(char),
-}
-
 #[derive(Debug, Clone)]
 struct Grid {
-  cells: Vec<Vec<Cell>>,
+  width: usize,
+  height: usize,
 }
 
 impl Grid {
   fn in_bounds(&self, position: &Position) -> bool {
-    position.0 < self.width() && position.1 < self.height()
-  }
-
-  fn get(&self, position: &Position) -> Cell {
-    let Position(x, y) = position;
-    self.cells[*y][*x]
-  }
-
-  fn set(&mut self, position: &Position, cell: Cell) {
-    let Position(x, y) = position;
-    self.cells[*y][*x] = cell;
-  }
-
-  fn width(&self) -> usize {
-    self.cells[0].len()
-  }
-
-  fn height(&self) -> usize {
-    self.cells.len()
+    position.0 < self.width && position.1 < self.height
   }
+}
 
-  fn pretty_print(&self) {
-    for row in self.cells.iter() {
-      for cell in row.iter() {
-        match cell {
-          Cell::Empty => print!("."),
-          Cell::Antenna(c) => print!("{}", c),
-        }
+fn parse(input: &str) -> io::Result<In> {
+  let mut antennas = HashMap::<char, Vec<Position>>::new();
+  let mut grid = Grid {
+    width: 0,
+    height: 0,
+  };
+
+  for (y, line) in input.lines().enumerate() {
+    let line = line.trim();
+    for (x, c) in line.chars().enumerate() {
+      if c.is_alphanumeric() {
+        antennas.entry(c).or_default().push(Position(x, y));
+      } else if c != '.' {
+        panic!("unexpected character")
       }
-      println!();
     }
-  }
-}
 
-fn input() -> io::Result<In> {
-  let mut antennas = HashMap::<char, Vec<Position>>::new();
-  let cells = read_day(8)?
-    .enumerate()
-    .map(|(y, line)| {
-      line
-        .trim()
-        .chars()
-        .enumerate()
-        .map(|(x, c)| {
-          if c.is_alphanumeric() {
-            antennas
-              .entry(c)
-              .or_insert_with(Vec::new)
-              .push(Position(x, y));
-            Cell::Antenna(c)
-          } else if c == '.' {
-            Cell::Empty
-          } else {
-            panic!("unexpected character")
-          }
-        })
-        .collect::<Vec<_>>()
-    })
-    .collect::<Vec<_>>();
+    grid.width = grid.width.max(line.chars().count());
+    grid.height = y + 1;
+  }
 
-  Ok(In {
-    grid: Grid { cells },
-    antennas,
-  })
+  Ok(In { grid, antennas })
 }
 
-fn two_to_one_ratio(grid: &Grid, positions: &Vec<Position>) -> Vec<Position> {
+fn two_to_one_ratio(grid: &Grid, positions: &[Position]) -> Vec<Position> {
   positions
-    .into_iter()
-    .flat_map(|a| positions.into_iter().map(|b| (*a, *b)))
+    .iter()
+    .flat_map(|a| positions.iter().map(|b| (*a, *b)))
     .filter(|(a, b)| *a != *b)
     .flat_map(|(a, b)| {
       let dx = a.0 as i32 - b.0 as i32;
@@ -134,10 +91,10 @@ fn two_to_one_ratio(grid: &Grid, positions: &Vec<Position>) -> Vec<Position> {
     .collect()
 }
 
-fn colinear(grid: &Grid, positions: &Vec<Position>) -> Vec<Position> {
+fn colinear(grid: &Grid, positions: &[Position]) -> Vec<Position> {
   positions
-    .into_iter()
-    .flat_map(|a| positions.into_iter().map(|b| (*a, *b)))
+    .iter()
+    .flat_map(|a| positions.iter().map(|b| (*a, *b)))
     .filter(|(a, b)| *a != *b)
     .flat_map(|(a, b)| {
       let dx = a.0 as i32 - b.0 as i32;
@@ -162,25 +119,75 @@ fn colinear(grid: &Grid, positions: &Vec<Position>) -> Vec<Position> {
     .collect::<Vec<_>>()
 }
 
-pub fn part_1() -> io::Result<Out> {
-  let input = input()?;
+fn part_1(input: &In) -> io::Result<Out> {
   let In { grid, antennas } = input;
 
   let result = antennas
-    .iter()
-    .flat_map(|(_, positions)| two_to_one_ratio(&grid, positions))
+    .values()
+    .flat_map(|positions| two_to_one_ratio(grid, positions))
     .collect::<HashSet<_>>();
   Ok(result.len())
 }
 
-pub fn part_2() -> io::Result<Out> {
-  let input = input()?;
+fn part_2(input: &In) -> io::Result<Out> {
   let In { grid, antennas } = input;
 
   let result = antennas
-    .iter()
-    .flat_map(|(_, positions)| colinear(&grid, positions))
+    .values()
+    .flat_map(|positions| colinear(grid, positions))
     .collect::<HashSet<_>>();
 
   Ok(result.len())
 }
+
+const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+pub struct Day08;
+
+impl Solution for Day08 {
+  const DAY: u8 = 8;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("14"),
+    part_2: Some("34"),
+  }];
+
+  type Input = In;
+  type Output = Out;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<Out> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<Out> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 14);
+    assert_eq!(part_2(&input).unwrap(), 34);
+  }
+}
@@ -2,13 +2,13 @@ use std::{
   array,
   collections::{BTreeSet, HashMap},
   fmt::Debug,
-  io, u8,
+  io,
 };
 
-use crate::fs::read_day;
+use crate::solution::{Example, Solution};
 
 #[derive(Clone, Copy)]
-enum Block {
+pub enum Block {
   File { id: usize, head: usize, size: u8 },
   FreeSpace { head: usize, size: u8 },
 }
@@ -26,22 +26,6 @@ impl Debug for Block {
   }
 }
 
-impl Block {
-  fn pretty_print(blocks: &Vec<Block>) -> String {
-    blocks
-      .iter()
-      .map(|block| match block {
-        Block::File { id, size, .. } => (0..*size)
-          .map(move |_| format!("{}", id))
-          .collect::<Vec<_>>()
-          .concat(),
-        Block::FreeSpace { size, .. } => (0..*size).map(move |_| ".").collect::<Vec<_>>().concat(),
-      })
-      .collect::<Vec<_>>()
-      .concat()
-  }
-}
-
 fn blocks_from_string(line: String) -> Vec<Block> {
   let mut id = 0;
   let mut head = 0;
@@ -65,16 +49,16 @@ fn blocks_from_string(line: String) -> Vec<Block> {
     .collect::<Vec<_>>()
 }
 
-fn input() -> io::Result<String> {
-  let line = read_day(9)?.map(|line| line.trim().to_string()).next();
+fn parse(input: &str) -> io::Result<Vec<Block>> {
+  let line = input.lines().next().map(|line| line.trim().to_string());
 
   match line {
-    Some(line) => Ok(line),
-    None => io::Result::Err(io::Error::new(io::ErrorKind::Other, "no input")),
+    Some(line) => Ok(blocks_from_string(line)),
+    None => io::Result::Err(io::Error::other("no input")),
   }
 }
 
-fn block_compact(blocks: &mut Vec<Option<usize>>) {
+fn block_compact(blocks: &mut [Option<usize>]) {
   let mut left_index = 0;
   let mut right_index = blocks.len() - 1;
 
@@ -101,7 +85,7 @@ fn block_compact(blocks: &mut Vec<Option<usize>>) {
   }
 }
 
-fn checksum(ids: &Vec<Option<usize>>) -> usize {
+fn checksum(ids: &[Option<usize>]) -> usize {
   ids
     .iter()
     .enumerate()
@@ -109,8 +93,7 @@ fn checksum(ids: &Vec<Option<usize>>) -> usize {
     .sum()
 }
 
-pub fn part_1() -> io::Result<usize> {
-  let input = blocks_from_string(input()?);
+fn part_1(input: &[Block]) -> io::Result<usize> {
   let mut ids = input
     .iter()
     .flat_map(|block| match block {
@@ -154,8 +137,8 @@ impl State {
         Block::FreeSpace { size, .. } => {
           if *size != 0 {
             // this hole can fit a file of size i or smaller
-            for i in 1..=*size as usize {
-              holes[i].insert(index);
+            for hole in holes.iter_mut().take(*size as usize + 1).skip(1) {
+              hole.insert(index);
             }
           }
         }
@@ -202,7 +185,7 @@ impl State {
           size: file_size,
         };
 
-        for i in 1..10 as usize {
+        for i in 1..10 {
           self.holes[i].remove(&hole_index);
         }
 
@@ -236,7 +219,7 @@ impl State {
 
         // This insert breaks the index of all the holes after the hole we just split.
 
-        for i in 1..10 as usize {
+        for i in 1..10 {
           self.holes[i].remove(&hole_index);
         }
 
@@ -275,9 +258,8 @@ impl State {
   }
 }
 
-pub fn part_2() -> io::Result<usize> {
-  let input = blocks_from_string(input()?);
-  let mut state = State::new(input);
+fn part_2(input: &[Block]) -> io::Result<usize> {
+  let mut state = State::new(input.to_vec());
   let max_file_id = state
     .files
     .keys()
@@ -303,10 +285,43 @@ pub fn part_2() -> io::Result<usize> {
   Ok(result)
 }
 
+pub struct Day09;
+
+impl Solution for Day09 {
+  const DAY: u8 = 9;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: "2333133121414131402",
+    part_1: Some("1928"),
+    part_2: Some("2858"),
+  }];
+
+  type Input = Vec<Block>;
+  type Output = usize;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<usize> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<usize> {
+    part_2(input)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_example() {
+    let input = parse("2333133121414131402").unwrap();
+    assert_eq!(part_1(&input).unwrap(), 1928);
+    assert_eq!(part_2(&input).unwrap(), 2858);
+  }
+
   #[test]
   fn test_part_1() {
     let input = blocks_from_string("2333133121414131402".to_string());
@@ -4,23 +4,23 @@ use std::{
   io, vec,
 };
 
-use crate::fs::read_day;
+use crate::{
+  fs,
+  solution::{Example, Solution},
+};
 
-struct Grid {
-  cells: Vec<Vec<u32>>,
+// Heights are kept as the digits they're written with, `.` is ground that
+// can't be walked on.
+pub struct Grid {
+  cells: fs::Grid,
   trailheads: Vec<Position>,
 }
 
 impl Debug for Grid {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    for row in &self.cells {
-      for cell in row {
-        let repr = if cell == &10 {
-          '.'
-        } else {
-          std::char::from_digit(*cell, 10).unwrap()
-        };
-        write!(f, "{}", repr)?;
+    for y in 0..self.cells.height() {
+      for x in 0..self.cells.width() {
+        write!(f, "{}", self.cells[(x, y)] as char)?;
       }
       writeln!(f)?;
     }
@@ -33,11 +33,15 @@ impl Debug for Grid {
 
 impl Grid {
   fn in_bounds(&self, &Position(x, y): &Position) -> bool {
-    y < self.cells.len() && x < self.cells[y].len()
+    self.cells.in_bounds(x, y)
   }
 
+  // `.` reads as 10, a height no trail can step up to.
   fn get(&self, &Position(x, y): &Position) -> Option<u32> {
-    self.cells.get(y).and_then(|row| row.get(x)).copied()
+    self.cells.get(x, y).map(|cell| match cell {
+      b'.' => 10,
+      digit => (digit - b'0') as u32,
+    })
   }
 }
 
@@ -94,33 +98,30 @@ impl Position {
   }
 }
 
-fn input() -> io::Result<Grid> {
+fn parse(input: &str) -> io::Result<Grid> {
+  let cells = fs::Grid::parse(input.as_bytes())?;
   let mut trailheads = Vec::new();
-  let result = read_day(10)?
-    .enumerate()
-    .map(|(y, line)| {
-      line
-        .trim()
-        .chars()
-        .enumerate()
-        .map(|(x, c)| {
-          let c = c
-            .to_digit(10)
-            .or(if c == '.' { Some(10) } else { None })
-            .expect("expected digit");
-          if c == 0 {
-            trailheads.push(Position(x, y));
-          }
-          c
-        })
-        .collect::<Vec<_>>()
-    })
-    .collect::<Vec<_>>();
 
-  Ok(Grid {
-    cells: result,
-    trailheads,
-  })
+  for y in 0..cells.height() {
+    for x in 0..cells.width() {
+      match cells[(x, y)] {
+        b'0' => trailheads.push(Position(x, y)),
+        b'1'..=b'9' | b'.' => {}
+        cell => {
+          return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+              "line {}: expected a height or '.', found '{}'",
+              y + 1,
+              cell.escape_ascii()
+            ),
+          ))
+        }
+      }
+    }
+  }
+
+  Ok(Grid { cells, trailheads })
 }
 
 struct Entry {
@@ -128,11 +129,7 @@ struct Entry {
   via: Vec<Position>,
 }
 
-fn dfs<'a>(
-  grid: &Grid,
-  position: &Position,
-  unique_9s: &'a mut HashMap<Position, HashSet<Position>>,
-) {
+fn dfs(grid: &Grid, position: &Position, unique_9s: &mut HashMap<Position, HashSet<Position>>) {
   let mut visited = HashSet::new();
   let mut stack: Vec<Entry> = vec![Entry {
     position: *position,
@@ -172,8 +169,7 @@ fn dfs<'a>(
   }
 }
 
-pub fn part_1() -> io::Result<usize> {
-  let grid = input()?;
+fn part_1(grid: &Grid) -> io::Result<usize> {
   let Grid { cells, trailheads } = grid;
   let mut unique_9s = HashMap::<Position, HashSet<Position>>::new();
 
@@ -183,7 +179,7 @@ pub fn part_1() -> io::Result<usize> {
         cells: cells.clone(),
         trailheads: Vec::new(),
       },
-      &trailhead,
+      trailhead,
       &mut unique_9s,
     );
   }
@@ -236,16 +232,62 @@ fn unique_subtrails_from(
   }
 }
 
-pub fn part_2() -> io::Result<usize> {
-  let grid = input()?;
-
+fn part_2(grid: &Grid) -> io::Result<usize> {
   let mut cache = HashMap::<Position, HashSet<Vec<Position>>>::new();
 
   let result = grid
     .trailheads
     .iter()
-    .map(|trailhead| unique_subtrails_from(&grid, trailhead, &mut cache).len())
+    .map(|trailhead| unique_subtrails_from(grid, trailhead, &mut cache).len())
     .sum();
 
   Ok(result)
 }
+
+const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+pub struct Day10;
+
+impl Solution for Day10 {
+  const DAY: u8 = 10;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("36"),
+    part_2: Some("81"),
+  }];
+
+  type Input = Grid;
+  type Output = usize;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> io::Result<usize> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> io::Result<usize> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 36);
+    assert_eq!(part_2(&input).unwrap(), 81);
+  }
+}
@@ -1,31 +1,29 @@
 use std::{
   collections::HashMap,
-  io::{Error, ErrorKind, Result},
+  io::{Error, Result},
 };
 
-use crate::fs::read_day;
+use crate::{
+  parse::{self, literal, preceded, separated, unsigned, whitespace},
+  solution::{Example, Solution},
+};
 
-fn input() -> Result<Vec<usize>> {
-  match read_day(11)?
-    .map(|line| {
-      line
-        .trim()
-        .split(" ")
-        .map(|s| s.parse::<usize>().expect("expected number"))
-        .collect()
-    })
+fn parse(input: &str) -> Result<Vec<usize>> {
+  let line = input
+    .lines()
     .next()
-  {
-    Some(vec) => Ok(vec),
-    None => Err(Error::new(ErrorKind::Other, "no input")),
-  }
+    .ok_or_else(|| Error::other("no input"))?;
+  parse::line(
+    (1, line),
+    preceded(whitespace(), separated(unsigned(), literal(" "))),
+  )
 }
 
 fn width(n: usize) -> usize {
   n.checked_ilog10().map_or_else(|| 1, |x| x + 1) as usize
 }
 
-fn brute_force_step(vec: &Vec<usize>) -> Vec<usize> {
+fn brute_force_step(vec: &[usize]) -> Vec<usize> {
   vec
     .iter()
     .flat_map(|&stone| {
@@ -33,7 +31,7 @@ fn brute_force_step(vec: &Vec<usize>) -> Vec<usize> {
         vec![1]
       } else {
         let width = width(stone);
-        if width % 2 == 0 {
+        if width.is_multiple_of(2) {
           let half = width / 2;
           let left = stone / 10usize.pow(half as u32);
           let right = stone % 10usize.pow(half as u32);
@@ -47,10 +45,9 @@ fn brute_force_step(vec: &Vec<usize>) -> Vec<usize> {
     .collect::<Vec<_>>()
 }
 
-pub fn part_1() -> Result<usize> {
-  let input = input()?;
+fn part_1(input: &[usize]) -> Result<usize> {
   let iterations = 25;
-  let result = (0..iterations).fold(input, |vec, _| brute_force_step(&vec));
+  let result = (0..iterations).fold(input.to_vec(), |vec, _| brute_force_step(&vec));
   Ok(result.len())
 }
 
@@ -59,7 +56,7 @@ type Steps = usize;
 
 fn count_steps(stone: Stone, steps: Steps, memo: &mut HashMap<(Stone, Steps), usize>) -> usize {
   if let Some(&result) = memo.get(&(stone, steps)) {
-    return result;
+    result
   } else {
     let result = if steps == 0 {
       1
@@ -67,7 +64,7 @@ fn count_steps(stone: Stone, steps: Steps, memo: &mut HashMap<(Stone, Steps), us
       count_steps(1, steps - 1, memo)
     } else {
       let width = width(stone);
-      if width % 2 == 0 {
+      if width.is_multiple_of(2) {
         let half = width / 2;
         let left = stone / 10usize.pow(half as u32);
         let right = stone % 10usize.pow(half as u32);
@@ -81,8 +78,7 @@ fn count_steps(stone: Stone, steps: Steps, memo: &mut HashMap<(Stone, Steps), us
   }
 }
 
-pub fn part_2() -> Result<usize> {
-  let input = input()?;
+fn part_2(input: &[usize]) -> Result<usize> {
   let iterations = 75;
   let mut memo = HashMap::new();
   let result = input
@@ -92,10 +88,42 @@ pub fn part_2() -> Result<usize> {
   Ok(result)
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+  const DAY: u8 = 11;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: "125 17",
+    part_1: Some("55312"),
+    part_2: None,
+  }];
+
+  type Input = Vec<usize>;
+  type Output = usize;
+
+  fn parse(input: &str) -> Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> Result<usize> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> Result<usize> {
+    part_2(input)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_example() {
+    let input = parse("125 17").unwrap();
+    assert_eq!(part_1(&input).unwrap(), 55312);
+  }
+
   #[test]
   fn test_width() {
     assert_eq!(width(0), 1);
@@ -1,13 +1,21 @@
-use std::{collections::HashSet, hash::Hash, io::Result, iter};
+use std::{collections::HashSet, io::Result};
 
-use crate::fs::read_day;
+use crate::{
+  fs::Grid,
+  solution::{Example, Solution},
+};
 
-fn input() -> Result<Vec<Vec<char>>> {
-  Ok(
-    read_day(12)?
-      .map(|line| line.trim().chars().collect())
-      .collect(),
-  )
+// Both parts work on the same regions, so they are found once up front.
+pub struct Garden {
+  grid: Grid,
+  regions: Vec<Region>,
+}
+
+fn parse(input: &str) -> Result<Garden> {
+  let grid = Grid::parse(input.as_bytes())?;
+  let regions = regions(&grid);
+
+  Ok(Garden { grid, regions })
 }
 
 type Position = (usize, usize);
@@ -49,23 +57,11 @@ impl PositionExt for Position {
   }
 }
 
-trait GridExt {
-  fn in_bounds(&self, position: Position) -> bool;
-}
-
-impl GridExt for Vec<Vec<char>> {
-  fn in_bounds(&self, position: Position) -> bool {
-    let (x, y) = position;
-    y < self.len() && x < self[y].len()
-  }
-}
-
 type Region = HashSet<Position>;
 
-fn flood_fill(grid: &Vec<Vec<char>>, position: Position, region: &mut Region) {
-  let (x_start, y_start) = position;
-  let cell = grid[y_start][x_start];
-  vec![
+fn flood_fill(grid: &Grid, position: Position, region: &mut Region) {
+  let cell = grid[position];
+  [
     position.up(),
     position.down(),
     position.left(),
@@ -73,8 +69,8 @@ fn flood_fill(grid: &Vec<Vec<char>>, position: Position, region: &mut Region) {
   ]
   .iter()
   .filter_map(|&position| position)
-  .filter(|&(x, y)| grid.in_bounds((x, y)))
-  .filter(|&(x, y)| grid[y][x] == cell)
+  .filter(|&(x, y)| grid.in_bounds(x, y))
+  .filter(|&position| grid[position] == cell)
   .for_each(|position| {
     if region.insert(position) {
       flood_fill(grid, position, region);
@@ -82,10 +78,10 @@ fn flood_fill(grid: &Vec<Vec<char>>, position: Position, region: &mut Region) {
   });
 }
 
-fn regions(grid: &Vec<Vec<char>>) -> Vec<Region> {
+fn regions(grid: &Grid) -> Vec<Region> {
   let mut seen = HashSet::<Position>::new();
-  let width = grid[0].len();
-  let height = grid.len();
+  let width = grid.width();
+  let height = grid.height();
 
   let mut regions = Vec::<Region>::new();
 
@@ -127,11 +123,9 @@ fn area(region: &Region) -> usize {
   region.len()
 }
 
-pub fn part_1() -> Result<usize> {
-  let grid = input()?;
-  let regions = regions(&grid);
-
-  let result = regions
+fn part_1(garden: &Garden) -> Result<usize> {
+  let result = garden
+    .regions
     .iter()
     .map(|region| area(region) * perimeter(region))
     .sum::<usize>();
@@ -154,7 +148,9 @@ fn sum_wrt(pos_1: Position, pos_2: Position, origin: Position) -> Position {
   )
 }
 
-fn corners(region: &HashSet<(usize, usize)>, grid: &Vec<Vec<char>>) -> usize {
+// the two kinds of corner are kept as separate branches to match the diagram
+#[allow(clippy::if_same_then_else)]
+fn corners(region: &HashSet<(usize, usize)>, grid: &Grid) -> usize {
   region
     .iter()
     .map(|position| {
@@ -173,12 +169,12 @@ fn corners(region: &HashSet<(usize, usize)>, grid: &Vec<Vec<char>>) -> usize {
       // X Y
       // X X
       // Type 1 is when two consecutive neighbors are in the region but their "sum" is not
-      let x = clockwise_pairs
+      clockwise_pairs
         .into_iter()
         .map(|(u, v)| {
           (
-            u.filter(|p| grid.in_bounds(*p)),
-            v.filter(|p| grid.in_bounds(*p)),
+            u.filter(|&(x, y)| grid.in_bounds(x, y)),
+            v.filter(|&(x, y)| grid.in_bounds(x, y)),
           )
         })
         .fold(0usize, |acc, (u, v)| {
@@ -195,31 +191,72 @@ fn corners(region: &HashSet<(usize, usize)>, grid: &Vec<Vec<char>>) -> usize {
           } else {
             acc
           }
-        });
-
-      x
+        })
     })
     .sum::<usize>()
 }
 
-fn region_crop(region: &Region, grid: &Vec<Vec<char>>) -> char {
-  let (px, py) = region.iter().next().unwrap();
-  grid[*py][*px]
-}
-
-pub fn part_2() -> Result<usize> {
-  let grid = input()?;
-  let regions = regions(&grid);
-
+fn part_2(garden: &Garden) -> Result<usize> {
   // count the corners
-  let result = regions
+  let result = garden
+    .regions
     .iter()
     .map(|region| {
       let area = area(region);
-      let corners = corners(region, &grid);
+      let corners = corners(region, &garden.grid);
       area * corners
     })
     .sum();
 
   Ok(result)
 }
+
+const EXAMPLE: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+pub struct Day12;
+
+impl Solution for Day12 {
+  const DAY: u8 = 12;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("1930"),
+    part_2: Some("1206"),
+  }];
+
+  type Input = Garden;
+  type Output = usize;
+
+  fn parse(input: &str) -> Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> Result<usize> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> Result<usize> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 1930);
+    assert_eq!(part_2(&input).unwrap(), 1206);
+  }
+}
@@ -1,9 +1,13 @@
-use std::io::Result;
+use std::io::{Error, ErrorKind, Result};
 
-use crate::fs::read_day;
+use crate::{
+  fs,
+  parse::{self, literal, pair, preceded, unsigned, Parser},
+  solution::{self, Example},
+};
 
 #[derive(Debug)]
-struct Problem {
+pub struct Problem {
   a: (u64, u64),
   b: (u64, u64),
   p: (u64, u64),
@@ -57,52 +61,49 @@ impl Solution {
   }
 }
 
-fn parse_one(lines: &[String]) -> Problem {
-  let a_line = &lines[0];
-  let b_line = &lines[1];
-  let p_line = &lines[2];
-
-  let a_line = a_line
-    .strip_prefix("Button A: X+")
-    .expect("Expected line to begin with 'Button A: X+'")
-    .split(", Y+")
-    .map(|num| num.parse::<u64>().expect("Expected number"))
-    .collect::<Vec<_>>();
-
-  let b_line = b_line
-    .strip_prefix("Button B: X+")
-    .expect("Expected line to begin with 'Button B: X+'")
-    .split(", Y+")
-    .map(|num| num.parse::<u64>().expect("Expected number"))
-    .collect::<Vec<_>>();
-
-  let p_line = p_line
-    .strip_prefix("Prize: X=")
-    .expect("Expected line to begin with 'Button P: X+'")
-    .split(", Y=")
-    .map(|num| num.parse::<u64>().expect("Expected number"))
-    .collect::<Vec<_>>();
-
-  Problem::new(
-    a_line[0], a_line[1], b_line[0], b_line[1], p_line[0], p_line[1],
+// `<name>: X<sign>94, Y<sign>34`, where the sign is `+` for a button's
+// offset and `=` for where the prize is.
+fn coordinates<'a>(name: &'static str, sign: &'static str) -> impl Parser<'a, (u64, u64)> {
+  preceded(
+    pair(literal(name), pair(literal(": X"), literal(sign))),
+    pair(
+      unsigned(),
+      preceded(pair(literal(", Y"), literal(sign)), unsigned()),
+    ),
   )
 }
 
-fn input() -> Result<Vec<Problem>> {
-  let lines = read_day(13)?
-    .collect::<Vec<_>>()
-    .split(|line| line.is_empty())
-    .collect::<Vec<_>>()
-    .into_iter()
-    .map(|x| parse_one(x))
-    .collect::<Vec<_>>();
+fn parse_one(section: fs::Section) -> Result<Problem> {
+  let invalid = |message: String| Error::new(ErrorKind::InvalidData, message);
+  let mut lines = section.lines();
+  let mut next = |name: &str| {
+    lines.next().ok_or_else(|| {
+      invalid(format!(
+        "machine on line {}: missing '{}' line",
+        section.line, name
+      ))
+    })
+  };
+
+  let (ax, ay) = parse::line(next("Button A")?, coordinates("Button A", "+"))?;
+  let (bx, by) = parse::line(next("Button B")?, coordinates("Button B", "+"))?;
+  let (px, py) = parse::line(next("Prize")?, coordinates("Prize", "="))?;
+
+  if let Some((number, _)) = lines.next() {
+    return Err(invalid(format!(
+      "line {}: expected a blank line after the prize",
+      number
+    )));
+  }
 
-  Ok(lines)
+  Ok(Problem::new(ax, ay, bx, by, px, py))
 }
 
-pub fn part_1() -> Result<u64> {
-  let problems = input()?;
+fn parse(input: &str) -> Result<Vec<Problem>> {
+  fs::sections(input).map(parse_one).collect()
+}
 
+fn part_1(problems: &[Problem]) -> Result<u64> {
   let result = problems
     .iter()
     .filter_map(|problem| problem.solve())
@@ -112,9 +113,7 @@ pub fn part_1() -> Result<u64> {
   Ok(result)
 }
 
-pub fn part_2() -> Result<u64> {
-  let problems = input()?;
-
+fn part_2(problems: &[Problem]) -> Result<u64> {
   let result = problems
     .iter()
     .map(|problem| Problem {
@@ -128,3 +127,92 @@ pub fn part_2() -> Result<u64> {
 
   Ok(result)
 }
+
+const EXAMPLE: &str = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+pub struct Day13;
+
+impl solution::Solution for Day13 {
+  const DAY: u8 = 13;
+  const EXAMPLES: &'static [Example] = &[Example {
+    input: EXAMPLE,
+    part_1: Some("480"),
+    part_2: None,
+  }];
+
+  type Input = Vec<Problem>;
+  type Output = u64;
+
+  fn parse(input: &str) -> Result<Self::Input> {
+    parse(input)
+  }
+
+  fn part_1(input: &Self::Input) -> Result<u64> {
+    part_1(input)
+  }
+
+  fn part_2(input: &Self::Input) -> Result<u64> {
+    part_2(input)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_example() {
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 480);
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    let error = |input: &str| parse(input).unwrap_err().to_string();
+
+    let machine = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n";
+    assert_eq!(
+      error(&format!("{}Prize: X=1, Y=1\n", machine)),
+      "line 4: expected a blank line after the prize"
+    );
+    assert_eq!(
+      error(&format!("{}\nButton A: X+1, Y+1\n", machine)),
+      "machine on line 5: missing 'Button B' line"
+    );
+    assert_eq!(
+      error("Button A: X+94, Y 34\n"),
+      "line 1, column 18: expected '+'"
+    );
+    assert_eq!(
+      error("Button A: X=94, Y+34\n"),
+      "line 1, column 12: expected '+'"
+    );
+    assert_eq!(
+      error("Button A: X94, Y+34\n"),
+      "line 1, column 12: expected '+'"
+    );
+    assert_eq!(
+      error("Button A: X+-94, Y+34\n"),
+      "line 1, column 13: expected a number"
+    );
+    assert_eq!(
+      error(&machine.replace("Prize: X=", "Prize: X+")),
+      "line 3, column 9: expected '='"
+    );
+  }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use crate::{
+  answers::{self, Answers, Verification},
+  cli::Part,
+  fs::{self, Source},
+  runner,
+  solution::DynSolution,
+};
+
+fn name(index: usize) -> String {
+  format!("example {}", index + 1)
+}
+
+// Every example of the given days, named by their position in the day's list.
+pub fn inputs(solutions: &[&'static dyn DynSolution]) -> Vec<(&'static dyn DynSolution, Source)> {
+  solutions
+    .iter()
+    .flat_map(|&solution| {
+      solution
+        .examples()
+        .iter()
+        .enumerate()
+        .map(move |(index, example)| {
+          let source = Source::Embedded {
+            name: name(index),
+            contents: example.input,
+          };
+          (solution, source)
+        })
+    })
+    .collect()
+}
+
+// The answers the puzzles give, keyed like `answers.txt` by the example's hash.
+pub fn answers(solutions: &[&'static dyn DynSolution]) -> Answers {
+  let mut answers = Answers::default();
+
+  for solution in solutions {
+    for example in solution.examples() {
+      for part in Part::all() {
+        if let Some(answer) = example.answer(part) {
+          let hash = fs::hash(example.input.as_bytes());
+          answers.insert(solution.day(), part, hash, answer.to_string());
+        }
+      }
+    }
+  }
+
+  answers
+}
+
+// Runs the examples, checking only the parts each example has an answer for.
+pub fn verify(
+  solutions: &[&'static dyn DynSolution],
+  parts: &[Part],
+  options: &runner::Options,
+) -> Vec<Verification> {
+  let answers = answers(solutions);
+
+  // An entry that failed to load has no hash of its own, so look up the
+  // example's instead. It is then kept, and reported as an error.
+  let hashes = solutions
+    .iter()
+    .flat_map(|solution| {
+      solution
+        .examples()
+        .iter()
+        .enumerate()
+        .map(|(index, example)| {
+          let hash = fs::hash(example.input.as_bytes());
+          ((solution.day(), name(index)), hash)
+        })
+    })
+    .collect::<HashMap<_, _>>();
+
+  let entries = runner::run(&inputs(solutions), parts, options)
+    .into_iter()
+    .filter(|entry| {
+      hashes
+        .get(&(entry.day, entry.input.clone()))
+        .is_some_and(|hash| answers.get(entry.day, entry.part, hash).is_some())
+    })
+    .collect();
+
+  answers::verify(entries, &answers)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io;
+
+  use crate::{
+    answers::Outcome,
+    solution::{Example, Solution, SOLUTIONS},
+  };
+
+  struct Broken;
+
+  impl Solution for Broken {
+    const DAY: u8 = 26;
+    const EXAMPLES: &'static [Example] = &[Example {
+      input: "1\n",
+      part_1: Some("1"),
+      part_2: None,
+    }];
+
+    type Input = ();
+    type Output = u8;
+
+    fn parse(_input: &str) -> io::Result<Self::Input> {
+      panic!("unexpected cell '{}'", '?')
+    }
+
+    fn part_1(_input: &Self::Input) -> io::Result<Self::Output> {
+      Ok(1)
+    }
+
+    fn part_2(_input: &Self::Input) -> io::Result<Self::Output> {
+      Ok(2)
+    }
+  }
+
+  #[test]
+  fn test_examples() {
+    for solution in SOLUTIONS {
+      assert!(
+        !solution.examples().is_empty(),
+        "day {} has no examples",
+        solution.day()
+      );
+    }
+
+    let failures = verify(SOLUTIONS, &Part::all(), &runner::Options::default())
+      .into_iter()
+      .filter(|verification| verification.outcome != Outcome::Pass)
+      .map(|Verification { entry, outcome }| {
+        format!(
+          "day {} part {} {}: {:?} {:?} ({})",
+          entry.day, entry.part, entry.input, entry.answer, outcome, entry.status
+        )
+      })
+      .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+  }
+
+  #[test]
+  fn test_broken_example() {
+    let verifications = verify(&[&Broken], &Part::all(), &runner::Options::default());
+
+    // part 2 has no answer to check, part 1 is reported even though parsing panicked
+    assert_eq!(verifications.len(), 1);
+    let Verification { entry, outcome } = &verifications[0];
+    assert_eq!((entry.day, entry.part), (26, Part::One));
+    assert_eq!(*outcome, Outcome::Error);
+    assert_eq!(
+      entry.status.to_string(),
+      "parse error: panicked: unexpected cell '?'"
+    );
+  }
+}
@@ -1,9 +1,482 @@
 use std::{
-  fs::File,
-  io::{self, BufRead},
+  env,
+  fmt::{self, Display},
+  fs::{self, File},
+  io::{self, ErrorKind, Read},
+  iter,
+  ops::Index,
+  path::PathBuf,
+  str,
+  sync::OnceLock,
 };
 
-pub fn read_day(day: u8) -> io::Result<impl Iterator<Item = String>> {
-  let file = File::open(format!("input/day_{:02}.txt", day))?;
-  Ok(io::BufReader::new(file).lines().flat_map(|line| line.ok()))
+pub fn day_file_name(day: u8) -> String {
+  format!("day_{:02}.txt", day)
+}
+
+// `input` relative to the working directory, unless `AOC_INPUT_DIR` says otherwise.
+pub fn input_dir() -> PathBuf {
+  env::var_os("AOC_INPUT_DIR").map_or_else(|| PathBuf::from("input"), PathBuf::from)
+}
+
+pub fn day_path(day: u8) -> PathBuf {
+  input_dir().join(day_file_name(day))
+}
+
+// Extra inputs for a day live in `day_NN/<name>.txt` next to its usual file.
+pub fn day_dir(day: u8) -> PathBuf {
+  input_dir().join(format!("day_{:02}", day))
+}
+
+// Every `.txt` file in the day's directory, sorted by name. A missing directory has none.
+pub fn input_set(day: u8) -> io::Result<Vec<PathBuf>> {
+  let entries = match fs::read_dir(day_dir(day)) {
+    Ok(entries) => entries,
+    Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+    Err(error) => return Err(error),
+  };
+
+  let mut paths = entries
+    .map(|entry| entry.map(|entry| entry.path()))
+    .collect::<io::Result<Vec<_>>>()?;
+  paths
+    .retain(|path| path.is_file() && path.extension().is_some_and(|extension| extension == "txt"));
+  paths.sort();
+
+  Ok(paths)
+}
+
+// Whether an input name is the one a day is read from by default.
+pub fn is_default_input(day: u8, name: &str) -> bool {
+  name == day_file_name(day)
+}
+
+// Where a day's input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+  Path(PathBuf),
+  Stdin,
+  // text compiled into the binary, like a puzzle's example
+  Embedded {
+    name: String,
+    contents: &'static str,
+  },
+}
+
+impl Source {
+  pub fn day(day: u8) -> Source {
+    Source::Path(day_path(day))
+  }
+
+  // `-` is stdin, anything else a path.
+  pub fn parse(s: &str) -> Source {
+    match s {
+      "-" => Source::Stdin,
+      path => Source::Path(PathBuf::from(path)),
+    }
+  }
+
+  // The name an input is reported under: relative to the input directory for
+  // files inside it, e.g. `day_07/edge_cases.txt`, otherwise just the file name.
+  pub fn name(&self) -> String {
+    match self {
+      Source::Path(path) => match path.strip_prefix(input_dir()) {
+        Ok(relative) => relative.display().to_string(),
+        Err(_) => path.file_name().map_or_else(
+          || path.display().to_string(),
+          |name| name.to_string_lossy().into_owned(),
+        ),
+      },
+      Source::Stdin => "stdin".to_string(),
+      Source::Embedded { name, .. } => name.clone(),
+    }
+  }
+
+  // The whole input as one owned buffer, see `read_bytes`. Stdin can only be
+  // read once, so it is kept around for every day that asks for it.
+  pub fn read_bytes(&self) -> io::Result<Vec<u8>> {
+    static STDIN: OnceLock<Result<Vec<u8>, (ErrorKind, String)>> = OnceLock::new();
+
+    let in_file = |error: io::Error| io::Error::new(error.kind(), format!("{}: {}", self, error));
+
+    match self {
+      Source::Path(path) => File::open(path).and_then(read_bytes).map_err(in_file),
+      Source::Stdin => STDIN
+        .get_or_init(|| {
+          read_bytes(io::stdin().lock()).map_err(|error| (error.kind(), error.to_string()))
+        })
+        .clone()
+        .map_err(|(kind, message)| in_file(io::Error::new(kind, message))),
+      Source::Embedded { contents, .. } => read_bytes(contents.as_bytes()),
+    }
+  }
+
+  // The same buffer as text. Nothing is copied, so a day that works on
+  // `input.as_bytes()` borrows straight from what was read.
+  pub fn read(&self) -> io::Result<String> {
+    let bytes = self.read_bytes()?;
+    text(bytes).map_err(|error| io::Error::new(error.kind(), format!("{}: {}", self, error)))
+  }
+}
+
+// The line a byte offset into `bytes` is on, counting from 1, and where that line starts.
+fn line_at(bytes: &[u8], offset: usize) -> (usize, usize) {
+  let before = &bytes[..offset];
+  let number = before.iter().filter(|&&byte| byte == b'\n').count() + 1;
+  let start = before
+    .iter()
+    .rposition(|&byte| byte == b'\n')
+    .map_or(0, |index| index + 1);
+  (number, start)
+}
+
+// Reads everything into one buffer, failing on a read error with the line it
+// happened on rather than handing back part of the input. A leading byte order
+// mark is dropped and CRLF line endings become LF, in place, so days only ever
+// see `\n`.
+pub fn read_bytes(mut reader: impl Read) -> io::Result<Vec<u8>> {
+  let mut bytes = Vec::new();
+  if let Err(error) = reader.read_to_end(&mut bytes) {
+    // whatever was read before the error is left in the buffer
+    let (line, _) = line_at(&bytes, bytes.len());
+    return Err(io::Error::new(
+      error.kind(),
+      format!("line {}: {}", line, error),
+    ));
+  }
+
+  let bom = if bytes.starts_with("\u{feff}".as_bytes()) {
+    3
+  } else {
+    0
+  };
+  let mut end = 0;
+  for index in bom..bytes.len() {
+    if bytes[index] == b'\r' && bytes.get(index + 1) == Some(&b'\n') {
+      continue;
+    }
+    bytes[end] = bytes[index];
+    end += 1;
+  }
+  bytes.truncate(end);
+
+  Ok(bytes)
+}
+
+// Checks the buffer is UTF-8 and hands it back as a `String` without copying,
+// naming the line of the first invalid byte otherwise.
+fn text(bytes: Vec<u8>) -> io::Result<String> {
+  String::from_utf8(bytes).map_err(|error| {
+    let offset = error.utf8_error().valid_up_to();
+    let (line, start) = line_at(error.as_bytes(), offset);
+    io::Error::new(
+      ErrorKind::InvalidData,
+      format!(
+        "line {}: invalid UTF-8 at byte {}",
+        line,
+        offset - start + 1
+      ),
+    )
+  })
+}
+
+// Lines, counting from 1, that end in spaces or tabs. Some days split on
+// whitespace and don't mind, others would read them as part of the input.
+pub fn trailing_whitespace(contents: &str) -> Vec<usize> {
+  contents
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| line.ends_with([' ', '\t']))
+    .map(|(index, _)| index + 1)
+    .collect()
+}
+
+impl Display for Source {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Source::Path(path) => write!(f, "{}", path.display()),
+      Source::Stdin => write!(f, "stdin"),
+      Source::Embedded { name, .. } => write!(f, "{}", name),
+    }
+  }
+}
+
+// Like `str::lines` for bytes, borrowing every line from the input. There is
+// no `\r` handling, `read_bytes` has already turned CRLF into LF.
+pub fn lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+  let mut lines = bytes
+    .strip_suffix(b"\n")
+    .unwrap_or(bytes)
+    .split(|&byte| byte == b'\n');
+  if bytes.is_empty() {
+    lines.next();
+  }
+  lines
+}
+
+// A run of lines between blank lines, with the line it starts on counting from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+  pub line: usize,
+  // without the line break after its last line
+  pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+  // The section's lines, each with its line number in the whole input.
+  pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> {
+    let start = self.line;
+    self
+      .text
+      .lines()
+      .enumerate()
+      .map(move |(index, line)| (start + index, line))
+  }
+}
+
+// Blocks of lines between blank lines, each with the line it starts on
+// counting from 1 and without the line break after its last line. Any number
+// of blank or whitespace-only lines may separate, lead or trail them.
+pub fn paragraphs(bytes: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+  let mut offset = 0;
+  let mut lines = bytes
+    .split_inclusive(|&byte| byte == b'\n')
+    .enumerate()
+    .map(move |(index, line)| {
+      let start = offset;
+      offset += line.len();
+      (index + 1, start, line)
+    })
+    .peekable();
+  let blank = |(_, _, line): &(usize, usize, &[u8])| line.trim_ascii().is_empty();
+
+  iter::from_fn(move || {
+    while lines.next_if(blank).is_some() {}
+    let &(line, start, _) = lines.peek()?;
+
+    let mut end = start;
+    while let Some((_, offset, text)) = lines.next_if(|line| !blank(line)) {
+      let text = text.strip_suffix(b"\n").unwrap_or(text);
+      end = offset + text.strip_suffix(b"\r").unwrap_or(text).len();
+    }
+
+    Some((line, &bytes[start..end]))
+  })
+}
+
+// `paragraphs` for text, as sections.
+pub fn sections(input: &str) -> impl Iterator<Item = Section<'_>> {
+  paragraphs(input.as_bytes()).map(move |(line, text)| {
+    // a paragraph is a slice of `input` cut next to line breaks, so its offset
+    // is always on a char boundary
+    let start = text.as_ptr() as usize - input.as_ptr() as usize;
+    Section {
+      line,
+      text: &input[start..start + text.len()],
+    }
+  })
+}
+
+// A rectangular block of text like a map, held as one buffer with no line
+// breaks and indexed by `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+  cells: Vec<u8>,
+  width: usize,
+  height: usize,
+}
+
+impl Grid {
+  // Every line has to be as wide as the first once trailing whitespace is trimmed.
+  pub fn parse(bytes: &[u8]) -> io::Result<Grid> {
+    let mut cells = Vec::with_capacity(bytes.len());
+    let mut width = None;
+    let mut height = 0;
+
+    for (index, line) in lines(bytes).enumerate() {
+      let line = line.trim_ascii_end();
+      match width {
+        None => width = Some(line.len()),
+        Some(width) if width != line.len() => {
+          return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            format!(
+              "line {} is {} wide, expected {}",
+              index + 1,
+              line.len(),
+              width
+            ),
+          ))
+        }
+        Some(_) => {}
+      }
+      cells.extend_from_slice(line);
+      height += 1;
+    }
+
+    Ok(Grid {
+      cells,
+      width: width.unwrap_or(0),
+      height,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+    x < self.width && y < self.height
+  }
+
+  pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+    self.in_bounds(x, y).then(|| self.cells[y * self.width + x])
+  }
+}
+
+impl Index<(usize, usize)> for Grid {
+  type Output = u8;
+
+  fn index(&self, (x, y): (usize, usize)) -> &u8 {
+    assert!(self.in_bounds(x, y), "({}, {}) is outside the grid", x, y);
+    &self.cells[y * self.width + x]
+  }
+}
+
+// 64 bit FNV-1a, stable across runs and platforms unlike `DefaultHasher`
+pub fn hash(bytes: &[u8]) -> String {
+  let hash = bytes.iter().fold(0xcbf29ce484222325_u64, |hash, &byte| {
+    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+  });
+  format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Fails after handing out `data`, like a disk going away mid-read.
+  struct Failing(&'static [u8]);
+
+  impl io::Read for Failing {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+      if self.0.is_empty() {
+        return Err(io::Error::other("device went away"));
+      }
+      let n = self.0.len().min(buf.len());
+      buf[..n].copy_from_slice(&self.0[..n]);
+      self.0 = &self.0[n..];
+      Ok(n)
+    }
+  }
+
+  #[test]
+  fn test_read_bytes() {
+    let read = |s: &str| text(read_bytes(s.as_bytes()).unwrap()).unwrap();
+    assert_eq!(read("\u{feff}3   4\r\n4   3\r\n"), "3   4\n4   3\n");
+    assert_eq!(read("a\nb"), "a\nb");
+    assert_eq!(read(""), "");
+    // only a CR right before a LF is part of a line ending
+    assert_eq!(read("a\rb\r\r\n"), "a\rb\r\n");
+    // a byte order mark only means something at the very start
+    assert_eq!(read("a\n\u{feff}b"), "a\n\u{feff}b");
+
+    let error = text(read_bytes(&b"1 2\r\n3 \xff4\n5 6\n"[..]).unwrap()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "line 2: invalid UTF-8 at byte 3");
+
+    let error = read_bytes(Failing(b"1 2\n3 4\n")).unwrap_err();
+    assert_eq!(error.to_string(), "line 3: device went away");
+  }
+
+  #[test]
+  fn test_source_read() {
+    let path = env::temp_dir().join(format!("aoc-read-{}.txt", std::process::id()));
+    fs::write(&path, b"ok\n\xc3\x28\n").unwrap();
+
+    let error = Source::Path(path.clone()).read().unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      format!("{}: line 2: invalid UTF-8 at byte 1", path.display())
+    );
+
+    fs::remove_file(&path).unwrap();
+  }
+
+  #[test]
+  fn test_lines() {
+    let lines = |s: &'static str| super::lines(s.as_bytes()).collect::<Vec<_>>();
+    for s in ["", "\n", "a", "a\n", "a\n\nb", "a\nb\n\n"] {
+      assert_eq!(
+        lines(s),
+        s.lines().map(str::as_bytes).collect::<Vec<_>>(),
+        "{:?}",
+        s
+      );
+    }
+  }
+
+  #[test]
+  fn test_paragraphs() {
+    let paragraphs = |s: &'static str| {
+      super::paragraphs(s.as_bytes())
+        .map(|(line, text)| (line, str::from_utf8(text).unwrap()))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      paragraphs("#.#\n..#\n\n\n##.\n"),
+      vec![(1, "#.#\n..#"), (5, "##.")]
+    );
+    assert_eq!(paragraphs("\t\r\na\r\n"), vec![(2, "a")]);
+    assert_eq!(paragraphs("\n\n"), vec![]);
+  }
+
+  #[test]
+  fn test_sections() {
+    let sections = |s| {
+      sections(s)
+        .map(|section| (section.line, section.text))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      sections("47|53\n97|13\n\n75,47\n"),
+      vec![(1, "47|53\n97|13"), (4, "75,47")]
+    );
+    assert_eq!(
+      sections("\n47|53\r\n \r\n\n75,47\r\n61,13\n\n\n"),
+      vec![(2, "47|53"), (5, "75,47\r\n61,13")]
+    );
+    assert_eq!(sections("\n \n"), vec![]);
+    assert_eq!(sections(""), vec![]);
+
+    let section = super::sections("a\n\nb\nc").nth(1).unwrap();
+    assert_eq!(
+      section.lines().collect::<Vec<_>>(),
+      vec![(3, "b"), (4, "c")]
+    );
+  }
+
+  #[test]
+  fn test_grid() {
+    let grid = Grid::parse(b"ab.\nc.d \n").unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 1)], b'd');
+    assert_eq!(grid.get(3, 0), None);
+    assert!(!grid.in_bounds(0, 2));
+
+    let error = Grid::parse(b"abc\nab\nabc").unwrap_err();
+    assert_eq!(error.to_string(), "line 2 is 2 wide, expected 3");
+  }
+
+  #[test]
+  fn test_trailing_whitespace() {
+    assert_eq!(trailing_whitespace("1 2\n3 4 \n5 6\n7 8\t"), vec![2, 4]);
+    assert_eq!(trailing_whitespace("1 2\n"), Vec::<usize>::new());
+  }
 }
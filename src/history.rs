@@ -0,0 +1,329 @@
+use std::{
+  collections::BTreeMap,
+  fs::{self, OpenOptions},
+  io::{self, Write},
+  path::Path,
+  process,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+  bench::Measurement,
+  table::{self, Align, Table},
+};
+
+pub const PATH: &str = ".aoc/history.csv";
+const HEADER: &str = "run,commit,day,part,median_ns";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+  // percentage a median may grow by before it is flagged
+  pub threshold: f64,
+  // number of previous runs the baseline is taken from
+  pub baseline: usize,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      threshold: 10.0,
+      baseline: 5,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+  pub run: u64,
+  pub commit: String,
+  pub day: u8,
+  pub part: String,
+  pub median: Duration,
+}
+
+fn commit() -> String {
+  process::Command::new("git")
+    .args(["rev-parse", "--short", "HEAD"])
+    .output()
+    .ok()
+    .filter(|output| output.status.success())
+    .and_then(|output| String::from_utf8(output.stdout).ok())
+    .map(|hash| hash.trim().to_string())
+    .filter(|hash| !hash.is_empty())
+    .unwrap_or_else(|| "unknown".to_string())
+}
+
+// Runs are numbered by when they started, but two runs in the same second
+// still need different numbers or `compare` can't tell them apart.
+fn next_run(path: &Path) -> io::Result<u64> {
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |now| now.as_secs());
+
+  let last = match load(path) {
+    Ok(records) => records.iter().map(|record| record.run).max(),
+    Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+    Err(error) => return Err(error),
+  };
+
+  Ok(last.map_or(now, |last| now.max(last + 1)))
+}
+
+pub fn append(path: &Path, measurements: &[Measurement]) -> io::Result<()> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+
+  let run = next_run(path)?;
+  let is_new = !path.exists();
+  let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+  if is_new {
+    writeln!(file, "{}", HEADER)?;
+  }
+
+  let commit = commit();
+
+  for measurement in measurements {
+    if let Ok(stats) = &measurement.stats {
+      writeln!(
+        file,
+        "{},{},{},{},{}",
+        run,
+        commit,
+        measurement.day,
+        measurement.stage,
+        stats.median.as_nanos()
+      )?;
+    }
+  }
+
+  Ok(())
+}
+
+fn parse_record(line: &str) -> Option<Record> {
+  match line.split(',').collect::<Vec<_>>().as_slice() {
+    [run, commit, day, part, median] => Some(Record {
+      run: run.parse().ok()?,
+      commit: commit.to_string(),
+      day: day.parse().ok()?,
+      part: part.to_string(),
+      median: Duration::from_nanos(median.parse().ok()?),
+    }),
+    _ => None,
+  }
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+  let contents = fs::read_to_string(path)?;
+
+  contents
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.is_empty() && *line != HEADER)
+    .map(|(index, line)| {
+      parse_record(line).ok_or_else(|| {
+        io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!(
+            "{}:{}: malformed record '{}'",
+            path.display(),
+            index + 1,
+            line
+          ),
+        )
+      })
+    })
+    .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+  New,
+  Ok,
+  Improvement,
+  Regression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+  pub day: u8,
+  pub part: String,
+  pub latest: Duration,
+  pub baseline: Option<Duration>,
+  pub change: Option<f64>,
+  pub verdict: Verdict,
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+  durations.sort();
+  durations[durations.len() / 2]
+}
+
+// Compares the most recent run against the median of the `baseline` runs before it.
+pub fn compare(records: &[Record], options: &Options) -> Vec<Comparison> {
+  let mut runs = records.iter().map(|record| record.run).collect::<Vec<_>>();
+  runs.sort();
+  runs.dedup();
+
+  let Some((&latest, previous)) = runs.split_last() else {
+    return Vec::new();
+  };
+  let previous = &previous[previous.len().saturating_sub(options.baseline)..];
+
+  let mut history = BTreeMap::<(u8, &str), Vec<Duration>>::new();
+  for record in records
+    .iter()
+    .filter(|record| previous.contains(&record.run))
+  {
+    history
+      .entry((record.day, record.part.as_str()))
+      .or_default()
+      .push(record.median);
+  }
+
+  records
+    .iter()
+    .filter(|record| record.run == latest)
+    .map(|record| {
+      let baseline = history
+        .get(&(record.day, record.part.as_str()))
+        .map(|durations| median(durations.clone()));
+
+      let change = baseline.map(|baseline| {
+        let baseline = baseline.as_nanos().max(1) as f64;
+        (record.median.as_nanos() as f64 - baseline) / baseline * 100.0
+      });
+
+      let verdict = match change {
+        None => Verdict::New,
+        Some(change) if change > options.threshold => Verdict::Regression,
+        Some(change) if change < -options.threshold => Verdict::Improvement,
+        Some(_) => Verdict::Ok,
+      };
+
+      Comparison {
+        day: record.day,
+        part: record.part.clone(),
+        latest: record.median,
+        baseline,
+        change,
+        verdict,
+      }
+    })
+    .collect()
+}
+
+pub fn report(comparisons: &[Comparison]) -> Table {
+  let mut table = Table::new(&[
+    ("day", Align::Right),
+    ("part", Align::Right),
+    ("baseline", Align::Right),
+    ("latest", Align::Right),
+    ("change", Align::Right),
+    ("status", Align::Left),
+  ]);
+
+  for comparison in comparisons {
+    table.row(vec![
+      comparison.day.to_string(),
+      comparison.part.clone(),
+      comparison
+        .baseline
+        .map_or_else(|| "-".to_string(), table::duration),
+      table::duration(comparison.latest),
+      comparison
+        .change
+        .map_or_else(|| "-".to_string(), |change| format!("{:+.1}%", change)),
+      match comparison.verdict {
+        Verdict::New => "new",
+        Verdict::Ok => "ok",
+        Verdict::Improvement => "improvement",
+        Verdict::Regression => "REGRESSION",
+      }
+      .to_string(),
+    ]);
+  }
+
+  table
+}
+
+#[cfg(test)]
+mod tests {
+  use std::env;
+
+  use super::*;
+  use crate::{bench::Stage, stats::Stats};
+
+  fn record(run: u64, day: u8, part: &str, millis: u64) -> Record {
+    Record {
+      run,
+      commit: "abc1234".to_string(),
+      day,
+      part: part.to_string(),
+      median: Duration::from_millis(millis),
+    }
+  }
+
+  #[test]
+  fn test_parse_record() {
+    assert_eq!(
+      parse_record("1700000000,abc1234,9,2,185000000"),
+      Some(record(1700000000, 9, "2", 185))
+    );
+    assert_eq!(parse_record("1700000000,abc1234,9,2"), None);
+  }
+
+  #[test]
+  fn test_compare() {
+    let records = vec![
+      record(1, 9, "2", 100),
+      record(1, 6, "2", 100),
+      record(2, 9, "2", 110),
+      record(2, 6, "2", 100),
+      record(3, 9, "2", 150),
+      record(3, 6, "2", 80),
+      record(3, 7, "1", 10),
+    ];
+
+    let verdicts = compare(&records, &Options::default())
+      .into_iter()
+      .map(|comparison| (comparison.day, comparison.verdict))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      verdicts,
+      vec![
+        (9, Verdict::Regression),
+        (6, Verdict::Improvement),
+        (7, Verdict::New)
+      ]
+    );
+  }
+
+  #[test]
+  fn test_append() {
+    let path = env::temp_dir().join(format!("aoc-history-{}.csv", process::id()));
+    let _ = fs::remove_file(&path);
+
+    let measurements = [Measurement {
+      day: 1,
+      input: "day_01.txt".to_string(),
+      stage: Stage::Parse,
+      stats: Ok(Stats::new(&[Duration::from_micros(5)]).unwrap()),
+      memory: None,
+    }];
+    // back to back, so both runs start in the same second
+    append(&path, &measurements).unwrap();
+    append(&path, &measurements).unwrap();
+
+    let records = load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].run, records[0].run + 1);
+    let comparisons = compare(&records, &Options::default());
+    assert_eq!(comparisons.len(), 1);
+    assert_eq!(comparisons[0].verdict, Verdict::Ok);
+  }
+}
@@ -0,0 +1,276 @@
+use std::{
+  io::{self, BufRead, BufReader, ErrorKind, Write},
+  net::{TcpStream, ToSocketAddrs},
+  time::Duration,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+// Only plain `http://` is supported, anything served over TLS needs a local proxy in front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+  pub host: String,
+  pub port: u16,
+  // prefix every request path is appended to, without a trailing slash
+  pub base: String,
+}
+
+impl Url {
+  pub fn parse(s: &str) -> Result<Url, String> {
+    let Some(rest) = s.strip_prefix("http://") else {
+      return Err(format!("'{}' is not an http:// url", s));
+    };
+
+    let (authority, base) = match rest.find('/') {
+      Some(index) => rest.split_at(index),
+      None => (rest, ""),
+    };
+
+    let (host, port) = match authority.rsplit_once(':') {
+      Some((host, port)) => (
+        host,
+        port
+          .parse::<u16>()
+          .map_err(|_| format!("invalid port in '{}'", s))?,
+      ),
+      None => (authority, 80),
+    };
+
+    if host.is_empty() {
+      return Err(format!("no host in '{}'", s));
+    }
+
+    Ok(Url {
+      host: host.to_string(),
+      port,
+      base: base.trim_end_matches('/').to_string(),
+    })
+  }
+
+  // What goes in the `Host` header, which carries the port unless it's the default.
+  pub fn authority(&self) -> String {
+    match self.port {
+      80 => self.host.clone(),
+      port => format!("{}:{}", self.host, port),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+  pub status: u16,
+  pub reason: String,
+  pub headers: Vec<(String, String)>,
+  pub body: Vec<u8>,
+}
+
+impl Response {
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(key, _)| key.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+
+  pub fn text(&self) -> String {
+    String::from_utf8_lossy(&self.body).into_owned()
+  }
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+  io::Error::new(ErrorKind::InvalidData, message.into())
+}
+
+// Sends a single request on a fresh connection, which is closed afterwards.
+pub fn request(
+  method: &str,
+  url: &Url,
+  path: &str,
+  headers: &[(&str, &str)],
+  body: Option<&[u8]>,
+) -> io::Result<Response> {
+  let address = (url.host.as_str(), url.port)
+    .to_socket_addrs()?
+    .next()
+    .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("no address for {}", url.host)))?;
+
+  let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+  stream.set_read_timeout(Some(TIMEOUT))?;
+  stream.set_write_timeout(Some(TIMEOUT))?;
+
+  let mut head = format!(
+    "{} {}{} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+    method,
+    url.base,
+    path,
+    url.authority()
+  );
+  for (name, value) in headers {
+    head.push_str(&format!("{}: {}\r\n", name, value));
+  }
+  if let Some(body) = body {
+    head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+  }
+  head.push_str("\r\n");
+
+  stream.write_all(head.as_bytes())?;
+  if let Some(body) = body {
+    stream.write_all(body)?;
+  }
+  stream.flush()?;
+
+  read_response(BufReader::new(stream))
+}
+
+fn read_line(reader: &mut impl BufRead) -> io::Result<String> {
+  let mut line = String::new();
+  if reader.read_line(&mut line)? == 0 {
+    return Err(invalid("connection closed in the middle of a response"));
+  }
+  Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn read_response(mut reader: impl BufRead) -> io::Result<Response> {
+  let status_line = read_line(&mut reader)?;
+  let mut status_parts = status_line.splitn(3, ' ');
+  let (Some(version), Some(status)) = (status_parts.next(), status_parts.next()) else {
+    return Err(invalid(format!("malformed status line '{}'", status_line)));
+  };
+  if !version.starts_with("HTTP/1.") {
+    return Err(invalid(format!("unsupported protocol '{}'", version)));
+  }
+  let status = status
+    .parse::<u16>()
+    .map_err(|_| invalid(format!("malformed status line '{}'", status_line)))?;
+  let reason = status_parts.next().unwrap_or_default().to_string();
+
+  let mut headers = Vec::new();
+  loop {
+    let line = read_line(&mut reader)?;
+    if line.is_empty() {
+      break;
+    }
+    match line.split_once(':') {
+      Some((name, value)) => headers.push((name.trim().to_string(), value.trim().to_string())),
+      None => return Err(invalid(format!("malformed header '{}'", line))),
+    }
+  }
+
+  let mut response = Response {
+    status,
+    reason,
+    headers,
+    body: Vec::new(),
+  };
+
+  let chunked = response
+    .header("Transfer-Encoding")
+    .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"));
+  let length = response.header("Content-Length").map(str::parse::<usize>);
+
+  response.body = match (chunked, length) {
+    (true, _) => read_chunked(&mut reader)?,
+    (false, Some(Ok(length))) => {
+      let mut body = vec![0; length];
+      reader.read_exact(&mut body)?;
+      body
+    }
+    (false, Some(Err(_))) => return Err(invalid("malformed Content-Length")),
+    // without either the body runs until the server closes the connection
+    (false, None) => {
+      let mut body = Vec::new();
+      reader.read_to_end(&mut body)?;
+      body
+    }
+  };
+
+  Ok(response)
+}
+
+fn read_chunked(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+  let mut body = Vec::new();
+
+  loop {
+    let line = read_line(reader)?;
+    // chunk extensions after a ';' carry nothing we need
+    let size = line.split(';').next().unwrap_or_default().trim();
+    let size = usize::from_str_radix(size, 16)
+      .map_err(|_| invalid(format!("malformed chunk size '{}'", line)))?;
+
+    if size == 0 {
+      // skip any trailers up to the closing empty line
+      while !read_line(reader)?.is_empty() {}
+      return Ok(body);
+    }
+
+    let start = body.len();
+    body.resize(start + size, 0);
+    reader.read_exact(&mut body[start..])?;
+    read_line(reader)?;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_url() {
+    assert_eq!(
+      Url::parse("http://127.0.0.1:8080/aoc/"),
+      Ok(Url {
+        host: "127.0.0.1".to_string(),
+        port: 8080,
+        base: "/aoc".to_string(),
+      })
+    );
+    assert_eq!(
+      Url::parse("http://adventofcode.com"),
+      Ok(Url {
+        host: "adventofcode.com".to_string(),
+        port: 80,
+        base: String::new(),
+      })
+    );
+    assert_eq!(
+      Url::parse("http://127.0.0.1:8080/aoc/")
+        .unwrap()
+        .authority(),
+      "127.0.0.1:8080"
+    );
+    assert_eq!(
+      Url::parse("http://adventofcode.com:80")
+        .unwrap()
+        .authority(),
+      "adventofcode.com"
+    );
+    assert!(Url::parse("https://adventofcode.com").is_err());
+    assert!(Url::parse("http://:80").is_err());
+  }
+
+  #[test]
+  fn test_read_response() {
+    let response =
+      read_response("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n1 2 3\nextra".as_bytes()).unwrap();
+    assert_eq!(
+      (response.status, response.text()),
+      (200, "1 2 3\n".to_string())
+    );
+
+    let response = read_response(
+      "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1 2 \r\n2;x=y\r\n3\n\r\n0\r\n\r\n"
+        .as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(response.text(), "1 2 3\n");
+
+    let response = read_response("HTTP/1.0 404 Not Found\r\n\r\nnot yet".as_bytes()).unwrap();
+    assert_eq!(
+      (response.status, response.reason.as_str(), response.text()),
+      (404, "Not Found", "not yet".to_string())
+    );
+
+    assert!(read_response("SSH-2.0\r\n\r\n".as_bytes()).is_err());
+  }
+}
@@ -0,0 +1,315 @@
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+  Null,
+  Bool(bool),
+  Number(f64),
+  String(String),
+  Array(Vec<Json>),
+  // members keep the order they appear in
+  Object(Vec<(String, Json)>),
+}
+
+impl Json {
+  pub fn get(&self, key: &str) -> Option<&Json> {
+    self
+      .as_object()?
+      .iter()
+      .find(|(name, _)| name == key)
+      .map(|(_, value)| value)
+  }
+
+  pub fn as_object(&self) -> Option<&[(String, Json)]> {
+    match self {
+      Json::Object(members) => Some(members),
+      _ => None,
+    }
+  }
+
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      Json::String(s) => Some(s),
+      _ => None,
+    }
+  }
+
+  // Only whole, non-negative numbers that fit.
+  pub fn as_u64(&self) -> Option<u64> {
+    match self {
+      Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 && *n <= u64::MAX as f64 => Some(*n as u64),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+  pub line: usize,
+  pub column: usize,
+  pub message: String,
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}:{}: {}", self.line, self.column, self.message)
+  }
+}
+
+struct Parser<'a> {
+  input: &'a str,
+  position: usize,
+}
+
+impl Parser<'_> {
+  fn error(&self, message: impl Into<String>) -> Error {
+    let before = &self.input[..self.position];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    Error {
+      line,
+      column,
+      message: message.into(),
+    }
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.input[self.position..].chars().next()
+  }
+
+  fn next(&mut self) -> Option<char> {
+    let c = self.peek()?;
+    self.position += c.len_utf8();
+    Some(c)
+  }
+
+  fn skip_whitespace(&mut self) {
+    while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+      self.position += 1;
+    }
+  }
+
+  fn expect(&mut self, expected: char) -> Result<(), Error> {
+    match self.peek() {
+      Some(c) if c == expected => {
+        self.position += 1;
+        Ok(())
+      }
+      Some(c) => Err(self.error(format!("expected '{}', found '{}'", expected, c))),
+      None => Err(self.error(format!("expected '{}', found the end", expected))),
+    }
+  }
+
+  fn literal(&mut self, word: &str, value: Json) -> Result<Json, Error> {
+    if self.input[self.position..].starts_with(word) {
+      self.position += word.len();
+      Ok(value)
+    } else {
+      Err(self.error("expected a value"))
+    }
+  }
+
+  fn value(&mut self) -> Result<Json, Error> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some('{') => self.object(),
+      Some('[') => self.array(),
+      Some('"') => Ok(Json::String(self.string()?)),
+      Some('t') => self.literal("true", Json::Bool(true)),
+      Some('f') => self.literal("false", Json::Bool(false)),
+      Some('n') => self.literal("null", Json::Null),
+      Some('-' | '0'..='9') => self.number(),
+      Some(c) => Err(self.error(format!("unexpected '{}'", c))),
+      None => Err(self.error("expected a value, found the end")),
+    }
+  }
+
+  fn object(&mut self) -> Result<Json, Error> {
+    self.expect('{')?;
+    let mut members = Vec::new();
+
+    self.skip_whitespace();
+    if self.peek() == Some('}') {
+      self.position += 1;
+      return Ok(Json::Object(members));
+    }
+
+    loop {
+      self.skip_whitespace();
+      if self.peek() != Some('"') {
+        return Err(self.error("expected a member name"));
+      }
+      let name = self.string()?;
+      self.skip_whitespace();
+      self.expect(':')?;
+      members.push((name, self.value()?));
+
+      self.skip_whitespace();
+      match self.peek() {
+        Some(',') => self.position += 1,
+        Some('}') => {
+          self.position += 1;
+          return Ok(Json::Object(members));
+        }
+        _ => return Err(self.error("expected ',' or '}' after a member")),
+      }
+    }
+  }
+
+  fn array(&mut self) -> Result<Json, Error> {
+    self.expect('[')?;
+    let mut values = Vec::new();
+
+    self.skip_whitespace();
+    if self.peek() == Some(']') {
+      self.position += 1;
+      return Ok(Json::Array(values));
+    }
+
+    loop {
+      values.push(self.value()?);
+
+      self.skip_whitespace();
+      match self.peek() {
+        Some(',') => self.position += 1,
+        Some(']') => {
+          self.position += 1;
+          return Ok(Json::Array(values));
+        }
+        _ => return Err(self.error("expected ',' or ']' after a value")),
+      }
+    }
+  }
+
+  fn hex(&mut self) -> Result<u32, Error> {
+    let digits = self.input.get(self.position..self.position + 4);
+    match digits.and_then(|digits| u32::from_str_radix(digits, 16).ok()) {
+      Some(code) => {
+        self.position += 4;
+        Ok(code)
+      }
+      None => Err(self.error("expected four hex digits after \\u")),
+    }
+  }
+
+  fn string(&mut self) -> Result<String, Error> {
+    self.expect('"')?;
+    let mut s = String::new();
+
+    loop {
+      match self.next() {
+        Some('"') => return Ok(s),
+        Some('\\') => match self.next() {
+          Some('"') => s.push('"'),
+          Some('\\') => s.push('\\'),
+          Some('/') => s.push('/'),
+          Some('b') => s.push('\u{8}'),
+          Some('f') => s.push('\u{c}'),
+          Some('n') => s.push('\n'),
+          Some('r') => s.push('\r'),
+          Some('t') => s.push('\t'),
+          Some('u') => {
+            let mut code = self.hex()?;
+            // characters outside the basic plane come as a surrogate pair
+            if (0xd800..0xdc00).contains(&code) && self.input[self.position..].starts_with("\\u") {
+              self.position += 2;
+              let low = self.hex()?;
+              code = 0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff);
+            }
+            s.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+          }
+          _ => return Err(self.error("invalid escape")),
+        },
+        Some(c) if (c as u32) < 0x20 => return Err(self.error("control character in a string")),
+        Some(c) => s.push(c),
+        None => return Err(self.error("unterminated string")),
+      }
+    }
+  }
+
+  fn number(&mut self) -> Result<Json, Error> {
+    let start = self.position;
+    while let Some('-' | '+' | '.' | 'e' | 'E' | '0'..='9') = self.peek() {
+      self.position += 1;
+    }
+
+    let text = &self.input[start..self.position];
+    match text.parse::<f64>() {
+      Ok(n) => Ok(Json::Number(n)),
+      Err(_) => {
+        self.position = start;
+        Err(self.error(format!("invalid number '{}'", text)))
+      }
+    }
+  }
+}
+
+pub fn parse(input: &str) -> Result<Json, Error> {
+  let mut parser = Parser { input, position: 0 };
+  let value = parser.value()?;
+
+  parser.skip_whitespace();
+  if parser.position < input.len() {
+    return Err(parser.error("unexpected data after the value"));
+  }
+
+  Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let json = parse(
+      r#"{
+        "event": "2024",
+        "members": {"12": {"name": null, "stars": 3, "ok": true}},
+        "list": [1.5, -2, 3e2, []],
+        "text": "a\"b\\né🎄"
+      }"#,
+    )
+    .unwrap();
+
+    assert_eq!(json.get("event").and_then(Json::as_str), Some("2024"));
+    let member = json.get("members").and_then(|members| members.get("12"));
+    assert_eq!(
+      member.and_then(|member| member.get("name")),
+      Some(&Json::Null)
+    );
+    assert_eq!(
+      member
+        .and_then(|member| member.get("stars"))
+        .and_then(Json::as_u64),
+      Some(3)
+    );
+    assert_eq!(
+      json.get("list"),
+      Some(&Json::Array(vec![
+        Json::Number(1.5),
+        Json::Number(-2.0),
+        Json::Number(300.0),
+        Json::Array(Vec::new())
+      ]))
+    );
+    assert_eq!(json.get("text").and_then(Json::as_str), Some("a\"b\\né🎄"));
+    assert_eq!(Json::Number(-2.0).as_u64(), None);
+  }
+
+  #[test]
+  fn test_errors() {
+    let error = |s: &str| parse(s).unwrap_err().to_string();
+
+    assert_eq!(
+      error("{\n  \"a\": 1,\n  \"b\" 2\n}"),
+      "3:7: expected ':', found '2'"
+    );
+    assert_eq!(error("[1, 2"), "1:6: expected ',' or ']' after a value");
+    assert_eq!(error("[1 2]"), "1:4: expected ',' or ']' after a value");
+    assert_eq!(error("\"abc"), "1:5: unterminated string");
+    assert_eq!(error("{} {}"), "1:4: unexpected data after the value");
+    assert_eq!(error("[1.2.3]"), "1:2: invalid number '1.2.3'");
+    assert_eq!(error(""), "1:1: expected a value, found the end");
+  }
+}
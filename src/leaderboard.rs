@@ -0,0 +1,379 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use crate::{
+  cli::Part,
+  json::{self, Json},
+  output::Record,
+  table::{Align, Table},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+  pub id: u64,
+  pub name: Option<String>,
+  pub local_score: u64,
+  pub stars: u64,
+  // unix timestamps of when each part's star was earned, by day
+  pub completions: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+  pub fn display_name(&self) -> String {
+    match &self.name {
+      Some(name) => name.clone(),
+      None => format!("(anonymous #{})", self.id),
+    }
+  }
+
+  fn last_star(&self) -> u64 {
+    self
+      .completions
+      .values()
+      .flatten()
+      .flatten()
+      .copied()
+      .max()
+      .unwrap_or(0)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+  pub year: u16,
+  // ranked, best local score first
+  pub members: Vec<Member>,
+}
+
+// Older exports quote their timestamps, newer ones don't.
+fn timestamp(value: &Json) -> Option<u64> {
+  value.as_u64().or_else(|| value.as_str()?.parse().ok())
+}
+
+fn member(id: &str, value: &Json) -> Result<Member, String> {
+  let invalid = |field: &str| format!("member {}: invalid {}", id, field);
+  let number = |field: &str| {
+    value
+      .get(field)
+      .and_then(Json::as_u64)
+      .ok_or_else(|| invalid(field))
+  };
+
+  let mut completions = BTreeMap::new();
+  let days = value
+    .get("completion_day_level")
+    .and_then(Json::as_object)
+    .ok_or_else(|| invalid("completion_day_level"))?;
+  for (day, parts) in days {
+    let day = day.parse::<u8>().map_err(|_| invalid("day"))?;
+    let mut stars = [None, None];
+    for (part, star) in parts.as_object().ok_or_else(|| invalid("day"))? {
+      let index = match part.parse::<Part>() {
+        Ok(Part::One) => 0,
+        Ok(Part::Two) => 1,
+        Err(_) => return Err(invalid("part")),
+      };
+      stars[index] = Some(
+        star
+          .get("get_star_ts")
+          .and_then(timestamp)
+          .ok_or_else(|| invalid("get_star_ts"))?,
+      );
+    }
+    completions.insert(day, stars);
+  }
+
+  Ok(Member {
+    id: id.parse().map_err(|_| invalid("id"))?,
+    name: value.get("name").and_then(Json::as_str).map(str::to_string),
+    local_score: number("local_score")?,
+    stars: number("stars")?,
+    completions,
+  })
+}
+
+pub fn parse(json: &Json) -> Result<Leaderboard, String> {
+  let year = json
+    .get("event")
+    .and_then(|event| event.as_str()?.parse().ok())
+    .ok_or("invalid event")?;
+
+  let mut members = json
+    .get("members")
+    .and_then(Json::as_object)
+    .ok_or("invalid members")?
+    .iter()
+    .map(|(id, value)| member(id, value))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  // ties go to whoever got their stars first
+  members.sort_by(|a, b| {
+    b.local_score
+      .cmp(&a.local_score)
+      .then(b.stars.cmp(&a.stars))
+      .then(a.last_star().cmp(&b.last_star()))
+      .then(a.id.cmp(&b.id))
+  });
+
+  Ok(Leaderboard { year, members })
+}
+
+pub fn load(path: &Path) -> io::Result<Leaderboard> {
+  let contents = fs::read_to_string(path)?;
+  let invalid = |message: String| {
+    io::Error::new(
+      io::ErrorKind::InvalidData,
+      format!("{}: {}", path.display(), message),
+    )
+  };
+
+  let json = json::parse(&contents).map_err(|error| invalid(format!("{}", error)))?;
+  parse(&json).map_err(invalid)
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+  // Howard Hinnant's days_from_civil, days since 1970-01-01
+  let year = if month <= 2 { year - 1 } else { year };
+  let era = year.div_euclid(400);
+  let year_of_era = year - era * 400;
+  let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+  let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+  era * 146097 + day_of_era - 719468
+}
+
+// Puzzles unlock at midnight EST, which is 05:00 UTC.
+fn unlock(year: u16, day: u8) -> u64 {
+  (days_from_civil(year as i64, 12, day as i64) * 86400 + 5 * 3600) as u64
+}
+
+fn clock(secs: u64) -> String {
+  let time = format!(
+    "{:02}:{:02}:{:02}",
+    secs / 3600 % 24,
+    secs / 60 % 60,
+    secs % 60
+  );
+  match secs / 86400 {
+    0 => time,
+    days => format!("{}d {}", days, time),
+  }
+}
+
+// The last day anyone has a star on.
+fn last_day(leaderboard: &Leaderboard) -> u8 {
+  leaderboard
+    .members
+    .iter()
+    .filter_map(|member| member.completions.keys().max().copied())
+    .max()
+    .unwrap_or(0)
+}
+
+fn rank(leaderboard: &Leaderboard, member: &Member) -> usize {
+  leaderboard
+    .members
+    .iter()
+    .filter(|other| other.local_score > member.local_score)
+    .count()
+    + 1
+}
+
+// `*` for both stars on a day, `+` for just the first and `.` for none.
+fn calendar(member: &Member, last_day: u8) -> String {
+  (1..=last_day)
+    .map(|day| match member.completions.get(&day) {
+      Some([_, Some(_)]) => '*',
+      Some([Some(_), None]) => '+',
+      _ => '.',
+    })
+    .collect()
+}
+
+pub fn ranking(leaderboard: &Leaderboard) -> Table {
+  let last_day = last_day(leaderboard);
+  let mut table = Table::new(&[
+    ("rank", Align::Right),
+    ("member", Align::Left),
+    ("score", Align::Right),
+    ("stars", Align::Right),
+    ("days", Align::Left),
+  ]);
+
+  for member in &leaderboard.members {
+    table.row(vec![
+      rank(leaderboard, member).to_string(),
+      member.display_name(),
+      member.local_score.to_string(),
+      member.stars.to_string(),
+      calendar(member, last_day),
+    ]);
+  }
+
+  table
+}
+
+struct Completion<'a> {
+  member: &'a Member,
+  day: u8,
+  // seconds after the puzzle unlocked
+  part_1: Option<u64>,
+  part_2: Option<u64>,
+}
+
+impl Completion<'_> {
+  fn delta(&self) -> Option<u64> {
+    Some(self.part_2?.saturating_sub(self.part_1?))
+  }
+}
+
+// Every day a member has a star on, by day and then by rank.
+fn completions(leaderboard: &Leaderboard) -> Vec<Completion<'_>> {
+  let mut completions = leaderboard
+    .members
+    .iter()
+    .flat_map(|member| {
+      member.completions.iter().map(move |(&day, stars)| {
+        let after_unlock =
+          |ts: Option<u64>| Some(ts?.saturating_sub(unlock(leaderboard.year, day)));
+        Completion {
+          member,
+          day,
+          part_1: after_unlock(stars[0]),
+          part_2: after_unlock(stars[1]),
+        }
+      })
+    })
+    .collect::<Vec<_>>();
+
+  // members are already ranked, a stable sort keeps them that way within a day
+  completions.sort_by_key(|completion| completion.day);
+  completions
+}
+
+pub fn details(leaderboard: &Leaderboard) -> Table {
+  let mut table = Table::new(&[
+    ("day", Align::Right),
+    ("member", Align::Left),
+    ("part 1", Align::Right),
+    ("part 2", Align::Right),
+    ("delta", Align::Right),
+  ]);
+
+  let mut last_day = None;
+  for completion in completions(leaderboard) {
+    if last_day.is_some_and(|day| day != completion.day) {
+      table.separator();
+    }
+    last_day = Some(completion.day);
+
+    let cell = |secs: Option<u64>| secs.map_or_else(|| "-".to_string(), clock);
+    table.row(vec![
+      completion.day.to_string(),
+      completion.member.display_name(),
+      cell(completion.part_1),
+      cell(completion.part_2),
+      cell(completion.delta()),
+    ]);
+  }
+
+  table
+}
+
+pub fn records(leaderboard: &Leaderboard) -> Vec<Record> {
+  completions(leaderboard)
+    .into_iter()
+    .map(|completion| {
+      let member = completion.member;
+      let star = |index: usize| member.completions[&completion.day][index];
+
+      Record::new()
+        .field("day", completion.day)
+        .field("member_id", member.id)
+        .field("member", member.display_name())
+        .field("rank", rank(leaderboard, member))
+        .field("local_score", member.local_score)
+        .field("part_1_ts", star(0))
+        .field("part_2_ts", star(1))
+        .field("part_1_secs", completion.part_1)
+        .field("part_2_secs", completion.part_2)
+        .field("delta_secs", completion.delta())
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXPORT: &str = r#"{
+    "event": "2024",
+    "owner_id": 1,
+    "members": {
+      "1": {
+        "id": 1, "name": "Ada", "stars": 3, "local_score": 5, "global_score": 0,
+        "last_star_ts": 1733119200,
+        "completion_day_level": {
+          "1": {"1": {"get_star_ts": 1733029500, "star_index": 1},
+                "2": {"get_star_ts": 1733029800, "star_index": 2}},
+          "2": {"1": {"get_star_ts": 1733119200, "star_index": 3}}
+        }
+      },
+      "2": {
+        "id": 2, "name": null, "stars": 2, "local_score": 3, "global_score": 0,
+        "last_star_ts": 1733120000,
+        "completion_day_level": {
+          "1": {"1": {"get_star_ts": "1733029260", "star_index": 0},
+                "2": {"get_star_ts": 1733120000, "star_index": 4}}
+        }
+      },
+      "3": {
+        "id": 3, "name": "Grace", "stars": 0, "local_score": 0, "global_score": 0,
+        "last_star_ts": 0, "completion_day_level": {}
+      }
+    }
+  }"#;
+
+  fn leaderboard() -> Leaderboard {
+    parse(&json::parse(EXPORT).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn test_unlock() {
+    assert_eq!(unlock(2024, 1), 1733029200);
+    assert_eq!(unlock(2023, 25), 1703480400);
+  }
+
+  #[test]
+  fn test_ranking() {
+    let expected = "\
+rank  member          score  stars  days
+----  --------------  -----  -----  ----
+   1  Ada                 5      3  *+
+   2  (anonymous #2)      3      2  *.
+   3  Grace               0      0  ..
+";
+    assert_eq!(ranking(&leaderboard()).to_string(), expected);
+  }
+
+  #[test]
+  fn test_details() {
+    let expected = "\
+day  member            part 1       part 2        delta
+---  --------------  --------  -----------  -----------
+  1  Ada             00:05:00     00:10:00     00:05:00
+  1  (anonymous #2)  00:01:00  1d 01:13:20  1d 01:12:20
+---  --------------  --------  -----------  -----------
+  2  Ada             01:00:00            -            -
+";
+    assert_eq!(details(&leaderboard()).to_string(), expected);
+  }
+
+  #[test]
+  fn test_invalid() {
+    let error = |s: &str| parse(&json::parse(s).unwrap()).unwrap_err();
+    assert_eq!(error(r#"{"members": {}}"#), "invalid event");
+    assert_eq!(
+      error(r#"{"event": "2024", "members": {"7": {"stars": 1}}}"#),
+      "member 7: invalid completion_day_level"
+    );
+  }
+}
@@ -1,3 +1,19 @@
+use std::{
+  path::Path,
+  process::{self, ExitCode},
+};
+
+use answers::Answers;
+use cli::{Command, Days, Part, Selection, VerifyOptions};
+use fs::Source;
+use output::{Format, Record};
+use solution::DynSolution;
+use table::Table;
+
+mod alloc;
+mod answers;
+mod bench;
+mod cli;
 mod day_01;
 mod day_02;
 mod day_03;
@@ -11,26 +27,386 @@ mod day_10;
 mod day_11;
 mod day_12;
 mod day_13;
+mod examples;
 mod fs;
+mod history;
+mod http;
+mod json;
+mod leaderboard;
+mod output;
+mod parse;
+mod pool;
+mod runner;
+mod scaffold;
+mod site;
+mod solution;
+mod stats;
+mod submit;
+mod table;
+mod watch;
 
-fn main() {
-  use day_13::*;
-  println!("Start");
-  let start = std::time::Instant::now();
-  let result_1 = part_1();
-  let checkpoint = std::time::Instant::now();
-  let result_2 = part_2();
-  let end = std::time::Instant::now();
+#[cfg(any(test, feature = "alloc"))]
+#[global_allocator]
+static ALLOCATOR: alloc::Counting = alloc::Counting;
 
-  println!(
-    "Part 1 took {:?}. Result: {:?}",
-    checkpoint.duration_since(start),
-    result_1
+// The selected solutions, each paired with every input it runs against.
+fn inputs(selection: &Selection) -> Result<Vec<(&'static dyn DynSolution, Source)>, String> {
+  let mut inputs = Vec::new();
+
+  for solution in solutions(selection)? {
+    for source in selection.sources(solution.day())? {
+      inputs.push((solution, source));
+    }
+  }
+
+  if selection.warn_whitespace {
+    warn_whitespace(&inputs);
+  }
+
+  Ok(inputs)
+}
+
+// Inputs that can't be read are reported when they're run, only warn here.
+fn warn_whitespace(inputs: &[(&'static dyn DynSolution, Source)]) {
+  for (_, source) in inputs {
+    let Ok(contents) = source.read() else {
+      continue;
+    };
+
+    let lines = fs::trailing_whitespace(&contents);
+    let mut listed = lines
+      .iter()
+      .take(5)
+      .map(|line| line.to_string())
+      .collect::<Vec<_>>()
+      .join(", ");
+    if lines.len() > 5 {
+      listed.push_str(&format!(" and {} more", lines.len() - 5));
+    }
+
+    if !lines.is_empty() {
+      eprintln!(
+        "warning: {}: trailing whitespace on line {}",
+        source, listed
+      );
+    }
+  }
+}
+
+fn check_alloc(alloc: bool) -> Result<(), String> {
+  if alloc && !alloc::INSTALLED {
+    return Err("--alloc needs the counting allocator, build with --features alloc".to_string());
+  }
+  Ok(())
+}
+
+fn solutions(selection: &Selection) -> Result<Vec<&'static dyn DynSolution>, String> {
+  let days = selection.days.resolve(&solution::days())?;
+  Ok(days.into_iter().filter_map(solution::find).collect())
+}
+
+fn exit_code(success: bool) -> ExitCode {
+  if success {
+    ExitCode::SUCCESS
+  } else {
+    ExitCode::FAILURE
+  }
+}
+
+fn print(format: Format, table: impl FnOnce() -> Table, records: impl FnOnce() -> Vec<Record>) {
+  match format {
+    Format::Table => print!("{}", table()),
+    Format::Json => print!("{}", output::json(&records())),
+    Format::Csv => print!("{}", output::csv(&records())),
+  }
+}
+
+fn run(selection: Selection, runner_options: runner::Options) -> Result<ExitCode, String> {
+  check_alloc(runner_options.alloc)?;
+  let entries = runner::run(&inputs(&selection)?, &selection.parts, &runner_options);
+
+  print(
+    selection.format,
+    || runner::summary(&entries),
+    || runner::records(&entries, runner_options.alloc),
   );
 
+  Ok(exit_code(entries.iter().all(|entry| entry.status.is_ok())))
+}
+
+fn bench(selection: Selection, options: bench::Options) -> Result<ExitCode, String> {
+  check_alloc(options.alloc)?;
+  let measurements = inputs(&selection)?
+    .into_iter()
+    .flat_map(|(solution, source)| bench::bench_day(solution, &source, &selection.parts, &options))
+    .collect::<Vec<_>>();
+
+  print(
+    selection.format,
+    || bench::report(&measurements),
+    || bench::records(&measurements, options.alloc),
+  );
+
+  // timings on other inputs aren't comparable with the recorded ones
+  let path = Path::new(history::PATH);
+  if !selection.inputs.is_empty() || selection.all_inputs {
+    eprintln!(
+      "not recorded in {}, only the usual inputs are",
+      path.display()
+    );
+  } else {
+    match history::append(path, &measurements) {
+      Ok(()) => eprintln!("recorded in {}", path.display()),
+      Err(error) => eprintln!("warning: could not record history: {}", error),
+    }
+  }
+
+  Ok(exit_code(
+    measurements
+      .iter()
+      .all(|measurement| measurement.stats.is_ok()),
+  ))
+}
+
+fn compare(options: history::Options) -> Result<ExitCode, String> {
+  let records = history::load(Path::new(history::PATH))
+    .map_err(|error| format!("could not read {}: {}", history::PATH, error))?;
+
+  let comparisons = history::compare(&records, &options);
+  print!("{}", history::report(&comparisons));
+
+  Ok(exit_code(comparisons.iter().all(|comparison| {
+    comparison.verdict != history::Verdict::Regression
+  })))
+}
+
+fn verify(
+  selection: Selection,
+  runner_options: runner::Options,
+  options: VerifyOptions,
+) -> Result<ExitCode, String> {
+  let path = Path::new(answers::PATH);
+  let answers = Answers::load(path).map_err(|error| error.to_string())?;
+
+  let entries = runner::run(&inputs(&selection)?, &selection.parts, &runner_options);
+
+  let verifications = answers::verify(entries, &answers);
+  print(
+    selection.format,
+    || answers::report(&verifications),
+    || answers::records(&verifications),
+  );
+
+  if options.record {
+    let recorded = answers::record(path, &verifications).map_err(|error| error.to_string())?;
+    eprintln!("recorded {} new answers in {}", recorded, path.display());
+  }
+
+  Ok(exit_code(answers::all_passed(&verifications)))
+}
+
+fn examples(selection: Selection, runner_options: runner::Options) -> Result<ExitCode, String> {
+  let verifications = examples::verify(&solutions(&selection)?, &selection.parts, &runner_options);
+  print(
+    selection.format,
+    || answers::report(&verifications),
+    || answers::records(&verifications),
+  );
+
+  Ok(exit_code(answers::all_passed(&verifications)))
+}
+
+fn fetch(days: Days, options: site::Options) -> Result<ExitCode, String> {
+  // unlike the other commands a day doesn't have to be implemented to fetch its input
+  let days = match days {
+    Days::All => solution::days(),
+    Days::Only(days) => days,
+  };
+
+  let client = site::Client::from_env(&options)?;
+  let mut success = true;
+
+  for day in days {
+    let path = fs::day_path(day);
+    match site::fetch(&client, day, &path) {
+      Ok(site::Fetched::Downloaded { bytes }) => {
+        println!(
+          "day {}: saved {} ({})",
+          day,
+          path.display(),
+          table::bytes(bytes as u64)
+        )
+      }
+      Ok(site::Fetched::Cached) => println!("day {}: already have {}", day, path.display()),
+      Err(error) => {
+        eprintln!("day {}: {}", day, error);
+        success = false;
+      }
+    }
+  }
+
+  Ok(exit_code(success))
+}
+
+fn submit(
+  day: u8,
+  part: Part,
+  runner_options: runner::Options,
+  options: site::Options,
+) -> Result<ExitCode, String> {
+  let solution = solution::find(day).ok_or_else(|| format!("day {} is not implemented", day))?;
+  let entry = runner::run(&[(solution, Source::day(day))], &[part], &runner_options)
+    .pop()
+    .expect("a single part was run");
+  let Some(answer) = entry.answer.clone() else {
+    return Err(format!("day {} part {}: {}", day, part, entry.status));
+  };
+
+  let log = Path::new(submit::PATH);
+  let submissions =
+    submit::load(log).map_err(|error| format!("could not read {}: {}", log.display(), error))?;
+  let now = submit::now();
+  if let Err(refusal) = submit::check(&submissions, day, part, &answer, now) {
+    return Err(format!("not submitting {}, {}", answer, refusal));
+  }
+
+  let client = site::Client::from_env(&options)?;
+  let page = client
+    .submit(day, part, &answer)
+    .map_err(|error| error.to_string())?;
+  let response = submit::parse_response(&page);
+
   println!(
-    "Part 2 took {:?}. Result: {:?}",
-    end.duration_since(checkpoint),
-    result_2
+    "day {} part {}: {}, {}",
+    day, part, answer, response.verdict
   );
+  if response.verdict == submit::Verdict::Unrecognised {
+    println!("{}", response.message);
+  }
+  if let Some(wait) = response.wait {
+    println!(
+      "the site asks to wait {}s before submitting again",
+      wait.as_secs()
+    );
+  }
+
+  let submission = submit::Submission {
+    time: now,
+    day,
+    part,
+    answer,
+    verdict: response.verdict,
+    wait: response.wait,
+  };
+  if let Err(error) = submit::append(log, &submission) {
+    eprintln!("warning: could not record submission: {}", error);
+  }
+
+  // a confirmed answer is worth keeping for verify
+  if response.verdict == submit::Verdict::Right {
+    let path = Path::new(answers::PATH);
+    let answers = Answers::load(path).map_err(|error| error.to_string())?;
+    let verifications = answers::verify(vec![entry], &answers);
+    if answers::record(path, &verifications).map_err(|error| error.to_string())? > 0 {
+      eprintln!("recorded in {}", path.display());
+    }
+  }
+
+  Ok(exit_code(response.verdict == submit::Verdict::Right))
+}
+
+fn leaderboard(path: &Path, format: Format) -> Result<ExitCode, String> {
+  let leaderboard = leaderboard::load(path).map_err(|error| error.to_string())?;
+
+  print(
+    format,
+    || leaderboard::ranking(&leaderboard),
+    || leaderboard::records(&leaderboard),
+  );
+  if format == Format::Table {
+    print!("\n{}", leaderboard::details(&leaderboard));
+  }
+
+  Ok(ExitCode::SUCCESS)
+}
+
+fn new(day: u8) -> Result<ExitCode, String> {
+  let written = scaffold::new_day(Path::new("src"), day).map_err(|error| error.to_string())?;
+  for path in &written {
+    println!("wrote {}", path.display());
+  }
+
+  // registering may leave over-long lines behind, let rustfmt tidy them up
+  // without following main.rs's `mod`s into the rest of the crate
+  let formatted = process::Command::new("rustfmt")
+    .args(["--edition", "2021", "--config", "skip_children=true"])
+    .args(&written)
+    .status()
+    .is_ok_and(|status| status.success());
+  if !formatted {
+    eprintln!("warning: could not run rustfmt, run cargo fmt");
+  }
+
+  let input = fs::day_path(day);
+  match scaffold::input_placeholder(&input) {
+    Ok(true) => println!("wrote {}, empty until fetched", input.display()),
+    Ok(false) => println!("already have {}", input.display()),
+    Err(error) => eprintln!("warning: could not create {}: {}", input.display(), error),
+  }
+
+  Ok(ExitCode::SUCCESS)
+}
+
+fn watch(
+  selection: Selection,
+  runner_options: runner::Options,
+  options: watch::Options,
+) -> Result<ExitCode, String> {
+  match inputs(&selection)?.as_slice() {
+    [(solution, Source::Path(path))] => watch::watch(
+      *solution,
+      path.clone(),
+      &selection.parts,
+      &runner_options,
+      &options,
+    ),
+    [(_, Source::Stdin)] => Err("watch can't poll stdin".to_string()),
+    _ => Err("watch takes a single day".to_string()),
+  }
+}
+
+fn main() -> ExitCode {
+  let command = match cli::parse(std::env::args().skip(1)) {
+    Ok(command) => command,
+    Err(message) => {
+      eprintln!("error: {}\n\n{}", message, cli::USAGE);
+      return ExitCode::from(2);
+    }
+  };
+
+  let result = match command {
+    Command::Help => {
+      println!("{}", cli::USAGE);
+      Ok(ExitCode::SUCCESS)
+    }
+    Command::Run(selection, runner_options) => run(selection, runner_options),
+    Command::Bench(selection, options) => bench(selection, options),
+    Command::Compare(options) => compare(options),
+    Command::Verify(selection, runner_options, options) => {
+      verify(selection, runner_options, options)
+    }
+    Command::Examples(selection, runner_options) => examples(selection, runner_options),
+    Command::Fetch(days, options) => fetch(days, options),
+    Command::Submit(day, part, runner_options, options) => {
+      submit(day, part, runner_options, options)
+    }
+    Command::Leaderboard(path, format) => leaderboard(&path, format),
+    Command::New(day) => new(day),
+    Command::Watch(selection, runner_options, options) => watch(selection, runner_options, options),
+  };
+
+  result.unwrap_or_else(|message| {
+    eprintln!("error: {}", message);
+    ExitCode::FAILURE
+  })
 }
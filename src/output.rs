@@ -0,0 +1,236 @@
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+  #[default]
+  Table,
+  Json,
+  Csv,
+}
+
+impl FromStr for Format {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "table" => Ok(Format::Table),
+      "json" => Ok(Format::Json),
+      "csv" => Ok(Format::Csv),
+      other => Err(format!("invalid format '{}'", other)),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+  Null,
+  Int(u128),
+  Str(String),
+}
+
+impl From<&str> for Value {
+  fn from(s: &str) -> Self {
+    Value::Str(s.to_string())
+  }
+}
+
+impl From<String> for Value {
+  fn from(s: String) -> Self {
+    Value::Str(s)
+  }
+}
+
+impl From<u8> for Value {
+  fn from(n: u8) -> Self {
+    Value::Int(n as u128)
+  }
+}
+
+impl From<u64> for Value {
+  fn from(n: u64) -> Self {
+    Value::Int(n as u128)
+  }
+}
+
+impl From<usize> for Value {
+  fn from(n: usize) -> Self {
+    Value::Int(n as u128)
+  }
+}
+
+impl From<Duration> for Value {
+  fn from(duration: Duration) -> Self {
+    Value::Int(duration.as_nanos())
+  }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+  fn from(value: Option<T>) -> Self {
+    value.map_or(Value::Null, Into::into)
+  }
+}
+
+// One row of machine readable output, fields keep the order they were added in.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Record {
+  fields: Vec<(&'static str, Value)>,
+}
+
+impl Record {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn field(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+    self.fields.push((name, value.into()));
+    self
+  }
+}
+
+fn json_string(s: &str) -> String {
+  let mut out = String::with_capacity(s.len() + 2);
+  out.push('"');
+  for c in s.chars() {
+    match c {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      '\t' => out.push_str("\\t"),
+      c if (c as u32) < 0x20 => {
+        let _ = write!(out, "\\u{:04x}", c as u32);
+      }
+      c => out.push(c),
+    }
+  }
+  out.push('"');
+  out
+}
+
+pub fn json(records: &[Record]) -> String {
+  let objects = records
+    .iter()
+    .map(|record| {
+      let fields = record
+        .fields
+        .iter()
+        .map(|(name, value)| {
+          let value = match value {
+            Value::Null => "null".to_string(),
+            Value::Int(n) => n.to_string(),
+            Value::Str(s) => json_string(s),
+          };
+          format!("{}: {}", json_string(name), value)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+      format!("  {{{}}}", fields)
+    })
+    .collect::<Vec<_>>();
+
+  if objects.is_empty() {
+    "[]\n".to_string()
+  } else {
+    format!("[\n{}\n]\n", objects.join(",\n"))
+  }
+}
+
+fn csv_cell(s: &str) -> String {
+  if s.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", s.replace('"', "\"\""))
+  } else {
+    s.to_string()
+  }
+}
+
+// The header has every field any record has, in the order they're first seen,
+// and a record without one of them leaves its cell empty.
+pub fn csv(records: &[Record]) -> String {
+  if records.is_empty() {
+    return String::new();
+  }
+
+  let mut names = Vec::new();
+  for (name, _) in records.iter().flat_map(|record| &record.fields) {
+    if !names.contains(name) {
+      names.push(*name);
+    }
+  }
+
+  let mut out = names
+    .iter()
+    .map(|name| csv_cell(name))
+    .collect::<Vec<_>>()
+    .join(",");
+  out.push('\n');
+
+  for record in records {
+    let line = names
+      .iter()
+      .map(|name| {
+        let value = record
+          .fields
+          .iter()
+          .find(|(field, _)| field == name)
+          .map(|(_, value)| value);
+        match value {
+          None | Some(Value::Null) => String::new(),
+          Some(Value::Int(n)) => n.to_string(),
+          Some(Value::Str(s)) => csv_cell(s),
+        }
+      })
+      .collect::<Vec<_>>()
+      .join(",");
+    out.push_str(&line);
+    out.push('\n');
+  }
+
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn records() -> Vec<Record> {
+    vec![
+      Record::new()
+        .field("day", 7u8)
+        .field("answer", "10741443549536")
+        .field("error", None::<String>),
+      Record::new()
+        .field("day", 6u8)
+        .field("answer", None::<String>)
+        .field("error", "unexpected \"cell\", line 3"),
+    ]
+  }
+
+  #[test]
+  fn test_json() {
+    let expected = "[\n  \
+      {\"day\": 7, \"answer\": \"10741443549536\", \"error\": null},\n  \
+      {\"day\": 6, \"answer\": null, \"error\": \"unexpected \\\"cell\\\", line 3\"}\n]\n";
+    assert_eq!(json(&records()), expected);
+    assert_eq!(json(&[]), "[]\n");
+  }
+
+  #[test]
+  fn test_csv() {
+    let expected = "day,answer,error\n\
+      7,10741443549536,\n\
+      6,,\"unexpected \"\"cell\"\", line 3\"\n";
+    assert_eq!(csv(&records()), expected);
+
+    let ragged = [
+      Record::new().field("day", 6u8).field("status", "panicked"),
+      Record::new()
+        .field("day", 7u8)
+        .field("allocations", 12u64)
+        .field("status", "ok"),
+    ];
+    assert_eq!(
+      csv(&ragged),
+      "day,status,allocations\n6,panicked,\n7,ok,12\n"
+    );
+  }
+}
@@ -0,0 +1,270 @@
+use std::{cmp::Ordering, io, str::FromStr};
+
+// Where a parser gave up and what it wanted there. The position is kept as how
+// much of the input was left, so parsers don't need to know where they started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+  remaining: usize,
+  expected: String,
+}
+
+// The value and whatever input follows it.
+pub type PResult<'a, T> = Result<(T, &'a str), Failure>;
+
+pub trait Parser<'a, T> {
+  fn parse(&self, input: &'a str) -> PResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+  F: Fn(&'a str) -> PResult<'a, T>,
+{
+  fn parse(&self, input: &'a str) -> PResult<'a, T> {
+    self(input)
+  }
+}
+
+fn fail<T>(input: &str, expected: impl Into<String>) -> Result<T, Failure> {
+  Err(Failure {
+    remaining: input.len(),
+    expected: expected.into(),
+  })
+}
+
+pub fn literal<'a>(text: &'static str) -> impl Parser<'a, &'a str> {
+  move |input: &'a str| match input.strip_prefix(text) {
+    Some(rest) => Ok((&input[..text.len()], rest)),
+    None => fail(input, format!("'{}'", text)),
+  }
+}
+
+// The number in the first `sign + digits` bytes of `input`.
+fn number<'a, T: FromStr>(input: &'a str, sign: usize) -> PResult<'a, T> {
+  let digits = input[sign..].bytes().take_while(u8::is_ascii_digit).count();
+  if digits == 0 {
+    return fail(input, "a number");
+  }
+
+  let (number, rest) = input.split_at(sign + digits);
+  match number.parse() {
+    Ok(number) => Ok((number, rest)),
+    Err(_) => fail(input, format!("a number that fits, not {}", number)),
+  }
+}
+
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<'a, T> {
+  move |input: &'a str| number(input, 0)
+}
+
+// A number with an optional `-` or `+` in front.
+#[allow(dead_code)]
+pub fn signed<'a, T: FromStr>() -> impl Parser<'a, T> {
+  move |input: &'a str| number(input, usize::from(input.starts_with(['-', '+'])))
+}
+
+// Any number of spaces or tabs, including none.
+pub fn whitespace<'a>() -> impl Parser<'a, ()> {
+  move |input: &'a str| Ok(((), input.trim_start_matches([' ', '\t'])))
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+  move |input: &'a str| {
+    let (value, rest) = parser.parse(input)?;
+    Ok((f(value), rest))
+  }
+}
+
+pub fn pair<'a, A, B>(
+  first: impl Parser<'a, A>,
+  second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+  move |input: &'a str| {
+    let (a, rest) = first.parse(input)?;
+    let (b, rest) = second.parse(rest)?;
+    Ok(((a, b), rest))
+  }
+}
+
+// `second`, after `first`.
+pub fn preceded<'a, A, B>(
+  first: impl Parser<'a, A>,
+  second: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+  map(pair(first, second), |(_, b)| b)
+}
+
+// `first`, followed by `second`.
+pub fn terminated<'a, A, B>(
+  first: impl Parser<'a, A>,
+  second: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+  map(pair(first, second), |(a, _)| a)
+}
+
+// `first`, or `second` if it fails. When both fail, the one that got further
+// says what went wrong.
+#[allow(dead_code)]
+pub fn alt<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+  move |input: &'a str| {
+    let first = match first.parse(input) {
+      Ok(parsed) => return Ok(parsed),
+      Err(failure) => failure,
+    };
+    let second = match second.parse(input) {
+      Ok(parsed) => return Ok(parsed),
+      Err(failure) => failure,
+    };
+
+    Err(match first.remaining.cmp(&second.remaining) {
+      Ordering::Less => first,
+      Ordering::Greater => second,
+      Ordering::Equal => Failure {
+        remaining: first.remaining,
+        expected: format!("{} or {}", first.expected, second.expected),
+      },
+    })
+  }
+}
+
+// One or more `item`s with a `separator` between each. An item must follow
+// every separator.
+pub fn separated<'a, T, S>(
+  item: impl Parser<'a, T>,
+  separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+  move |input: &'a str| {
+    let (first, mut rest) = item.parse(input)?;
+    let mut items = vec![first];
+
+    while let Ok((_, after)) = separator.parse(rest) {
+      let (next, after) = item.parse(after)?;
+      items.push(next);
+      rest = after;
+    }
+
+    Ok((items, rest))
+  }
+}
+
+// Runs `parser` over a line numbered from 1, which it has to use all of.
+// Trailing whitespace is let through, `--warn-whitespace` points it out.
+pub fn line<'a, T>((number, line): (usize, &'a str), parser: impl Parser<'a, T>) -> io::Result<T> {
+  let text = line.trim_end();
+  let failure = match parser.parse(text) {
+    Ok((value, "")) => return Ok(value),
+    Ok((_, rest)) => Failure {
+      remaining: rest.len(),
+      expected: "the end of the line".to_string(),
+    },
+    Err(failure) => failure,
+  };
+
+  let column = text[..text.len() - failure.remaining].chars().count() + 1;
+  Err(io::Error::new(
+    io::ErrorKind::InvalidData,
+    format!(
+      "line {}, column {}: expected {}",
+      number, column, failure.expected
+    ),
+  ))
+}
+
+// Every one of some numbered lines through `parser`.
+pub fn each<'a, T>(
+  lines: impl IntoIterator<Item = (usize, &'a str)>,
+  parser: impl Parser<'a, T>,
+) -> io::Result<Vec<T>> {
+  lines
+    .into_iter()
+    .map(|numbered| line(numbered, |input| parser.parse(input)))
+    .collect()
+}
+
+// Every line of `input` through `parser`.
+pub fn lines<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> io::Result<Vec<T>> {
+  each(
+    input
+      .lines()
+      .enumerate()
+      .map(|(index, text)| (index + 1, text)),
+    parser,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn error<'a, T>(text: &'a str, parser: impl Parser<'a, T>) -> String {
+    line((3, text), parser).err().unwrap().to_string()
+  }
+
+  #[test]
+  fn test_parsers() {
+    let equation = || {
+      pair(
+        terminated(unsigned::<u64>(), pair(literal(":"), whitespace())),
+        separated(unsigned::<u64>(), literal(" ")),
+      )
+    };
+    assert_eq!(
+      line((1, "190: 10 19"), equation()).unwrap(),
+      (190, vec![10, 19])
+    );
+    assert_eq!(line((1, "3:1 \t"), equation()).unwrap(), (3, vec![1]));
+
+    let position = || {
+      preceded(
+        pair(literal("X"), alt(literal("+"), literal("="))),
+        signed::<i32>(),
+      )
+    };
+    assert_eq!(line((1, "X+94"), position()).unwrap(), 94);
+    assert_eq!(line((1, "X=-7"), position()).unwrap(), -7);
+    assert_eq!(line((1, "X++7"), position()).unwrap(), 7);
+
+    let rule = pair(terminated(unsigned::<u8>(), literal("|")), unsigned::<u8>());
+    assert_eq!(
+      lines("47|53\n97|13\n", rule).unwrap(),
+      vec![(47, 53), (97, 13)]
+    );
+  }
+
+  #[test]
+  fn test_errors() {
+    let list = || separated(unsigned::<u32>(), literal(","));
+    assert_eq!(
+      error("1,2,x", list()),
+      "line 3, column 5: expected a number"
+    );
+    assert_eq!(
+      error("1,2;3", list()),
+      "line 3, column 4: expected the end of the line"
+    );
+    assert_eq!(
+      error("12,99999999999", list()),
+      "line 3, column 4: expected a number that fits, not 99999999999"
+    );
+
+    let sign = || alt(literal("+"), literal("="));
+    assert_eq!(
+      error("X-1", preceded(literal("X"), sign())),
+      "line 3, column 2: expected '+' or '='"
+    );
+    assert_eq!(
+      error("é: 1", pair(literal("é"), literal(": x"))),
+      "line 3, column 2: expected ': x'"
+    );
+    assert_eq!(
+      error(
+        "Prize: 5",
+        alt(preceded(literal("Prize: X="), unsigned::<u8>()), unsigned())
+      ),
+      "line 3, column 1: expected 'Prize: X=' or a number"
+    );
+    assert_eq!(
+      error("-", signed::<i8>()),
+      "line 3, column 1: expected a number"
+    );
+  }
+}
@@ -0,0 +1,58 @@
+use std::{
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+  },
+  thread,
+};
+
+pub fn default_jobs() -> usize {
+  thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
+// Maps `f` over `items` on up to `jobs` scoped threads. Results come back in the
+// order of `items`, no matter which thread finished first.
+pub fn map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+  let jobs = jobs.clamp(1, items.len().max(1));
+
+  if jobs == 1 {
+    return items.iter().map(f).collect();
+  }
+
+  let next = AtomicUsize::new(0);
+  let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+
+  thread::scope(|scope| {
+    for _ in 0..jobs {
+      scope.spawn(|| loop {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        let Some(item) = items.get(index) else {
+          break;
+        };
+        let result = f(item);
+        results.lock().unwrap()[index] = Some(result);
+      });
+    }
+  });
+
+  results
+    .into_inner()
+    .unwrap()
+    .into_iter()
+    .map(|result| result.expect("every item is mapped"))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_map_keeps_order() {
+    let items = (0..100u64).collect::<Vec<_>>();
+    let expected = items.iter().map(|x| x * x).collect::<Vec<_>>();
+    assert_eq!(map(&items, 8, |x| x * x), expected);
+    assert_eq!(map(&items, 1, |x| x * x), expected);
+    assert_eq!(map(&[] as &[u64], 4, |x| x * x), Vec::<u64>::new());
+  }
+}
@@ -0,0 +1,475 @@
+use std::{
+  any::Any,
+  fmt::{self, Display},
+  io,
+  panic::{self, AssertUnwindSafe},
+  sync::{mpsc, Arc, Once},
+  thread,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  alloc::{self, Usage},
+  cli::Part,
+  fs::{self, Source},
+  output::Record,
+  pool,
+  solution::{DynSolution, Parsed},
+  table::{self, Align, Table},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+  Ok,
+  // the input couldn't be read, so nothing was parsed
+  ReadError(String),
+  ParseError(String),
+  Error(String),
+  Panicked(String),
+  TimedOut(Duration),
+}
+
+impl Status {
+  pub fn is_ok(&self) -> bool {
+    matches!(self, Status::Ok)
+  }
+
+  pub fn label(&self) -> &'static str {
+    match self {
+      Status::Ok => "ok",
+      Status::ReadError(_) => "read error",
+      Status::ParseError(_) => "parse error",
+      Status::Error(_) => "error",
+      Status::Panicked(_) => "panicked",
+      Status::TimedOut(_) => "timed out",
+    }
+  }
+
+  pub fn message(&self) -> Option<String> {
+    match self {
+      Status::Ok => None,
+      Status::ReadError(message)
+      | Status::ParseError(message)
+      | Status::Error(message)
+      | Status::Panicked(message) => Some(message.clone()),
+      Status::TimedOut(timeout) => Some(format!("no answer after {:?}", timeout)),
+    }
+  }
+}
+
+impl Display for Status {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Status::Ok => write!(f, "ok"),
+      Status::ReadError(message) => write!(f, "read error: {}", message),
+      Status::ParseError(message) => write!(f, "parse error: {}", message),
+      Status::Error(message) => write!(f, "error: {}", message),
+      Status::Panicked(message) => write!(f, "panicked: {}", message),
+      Status::TimedOut(timeout) => write!(f, "timed out after {:?}", timeout),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+  pub day: u8,
+  pub part: Part,
+  pub input: String,
+  pub input_hash: Option<String>,
+  pub answer: Option<String>,
+  pub parse: Duration,
+  pub solve: Duration,
+  // what solving allocated, only counted when asked for
+  pub memory: Option<Usage>,
+  pub status: Status,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+  // number of threads days and parts are spread over, 1 runs everything serially
+  pub jobs: usize,
+  // wall clock limit for parsing and for each part, `None` waits forever
+  pub timeout: Option<Duration>,
+  // count allocations made while solving each part
+  pub alloc: bool,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      jobs: pool::default_jobs(),
+      timeout: Some(Duration::from_secs(60)),
+      alloc: false,
+    }
+  }
+}
+
+const WORKER: &str = "aoc-worker";
+
+// Solutions recurse freely (e.g. day 12's flood fill), so give them as much
+// stack as the main thread would have had.
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+enum Failure {
+  Panicked(String),
+  TimedOut(Duration),
+}
+
+impl Failure {
+  fn status(self) -> Status {
+    match self {
+      Failure::Panicked(message) => Status::Panicked(message),
+      Failure::TimedOut(timeout) => Status::TimedOut(timeout),
+    }
+  }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+  if let Some(message) = payload.downcast_ref::<&str>() {
+    message.to_string()
+  } else if let Some(message) = payload.downcast_ref::<String>() {
+    message.clone()
+  } else {
+    "unknown panic payload".to_string()
+  }
+}
+
+// Panics on worker threads are reported in the results, keep them off stderr.
+fn silence_worker_panics() {
+  static INSTALL: Once = Once::new();
+  INSTALL.call_once(|| {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+      if thread::current().name() != Some(WORKER) {
+        default(info);
+      }
+    }));
+  });
+}
+
+// Runs `f` on its own thread, turning a panic or running past `timeout` into a
+// `Failure`. A timed out thread cannot be stopped, it is left to finish in the
+// background while the run moves on.
+fn isolated<T: Send + 'static>(
+  timeout: Option<Duration>,
+  f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, Failure> {
+  silence_worker_panics();
+
+  let (sender, receiver) = mpsc::channel();
+  thread::Builder::new()
+    .name(WORKER.to_string())
+    .stack_size(WORKER_STACK_SIZE)
+    .spawn(move || {
+      let result = panic::catch_unwind(AssertUnwindSafe(f));
+      // the receiver is gone if we already timed out
+      let _ = sender.send(result);
+    })
+    .expect("failed to spawn worker thread");
+
+  let result = match timeout {
+    Some(timeout) => receiver
+      .recv_timeout(timeout)
+      .map_err(|_| Failure::TimedOut(timeout))?,
+    None => receiver.recv().expect("worker reports back before exiting"),
+  };
+
+  result.map_err(|payload| Failure::Panicked(panic_message(payload)))
+}
+
+struct Loaded {
+  solution: &'static dyn DynSolution,
+  name: String,
+  input_hash: Option<String>,
+  parse: Duration,
+  // how every part fails when there is no input to solve
+  input: Result<Arc<Parsed>, Status>,
+}
+
+fn load(solution: &'static dyn DynSolution, source: &Source, options: &Options) -> Loaded {
+  let name = source.name();
+  let source = source.clone();
+  let result = isolated(options.timeout, move || {
+    let contents = source.read()?;
+    // as in bench, the parse time doesn't include reading the input
+    let start = Instant::now();
+    let input = solution.parse(&contents);
+    let parse = start.elapsed();
+    io::Result::Ok((input, fs::hash(contents.as_bytes()), parse))
+  });
+
+  let (input, input_hash, parse) = match result {
+    Ok(Ok((input, hash, parse))) => (
+      input.map_err(|error| Status::ParseError(error.to_string())),
+      Some(hash),
+      parse,
+    ),
+    Ok(Err(error)) => (
+      Err(Status::ReadError(error.to_string())),
+      None,
+      Duration::ZERO,
+    ),
+    Err(failure) => (
+      Err(Status::ParseError(failure.status().to_string())),
+      None,
+      Duration::ZERO,
+    ),
+  };
+
+  Loaded {
+    solution,
+    name,
+    input_hash,
+    parse,
+    input: input.map(Arc::new),
+  }
+}
+
+fn solve(loaded: &Loaded, part: Part, options: &Options) -> Entry {
+  let (answer, solve, memory, status) = match &loaded.input {
+    Ok(input) => {
+      let solution = loaded.solution;
+      let input = input.clone();
+      let count = options.alloc;
+      let result = isolated(options.timeout, move || {
+        let timed = || {
+          let start = Instant::now();
+          let result = solution.solve(part, &input);
+          (result, start.elapsed())
+        };
+
+        if count {
+          let (timed, usage) = alloc::measure(timed);
+          (timed, Some(usage))
+        } else {
+          (timed(), None)
+        }
+      });
+
+      match result {
+        Ok(((Ok(answer), solve), memory)) => (Some(answer), solve, memory, Status::Ok),
+        Ok(((Err(error), solve), memory)) => {
+          (None, solve, memory, Status::Error(error.to_string()))
+        }
+        Err(failure) => (None, Duration::ZERO, None, failure.status()),
+      }
+    }
+    Err(status) => (None, Duration::ZERO, None, status.clone()),
+  };
+
+  Entry {
+    day: loaded.solution.day(),
+    part,
+    input: loaded.name.clone(),
+    input_hash: loaded.input_hash.clone(),
+    answer,
+    parse: loaded.parse,
+    solve,
+    memory,
+    status,
+  }
+}
+
+// Every input is parsed once, then all of the parts are solved against it.
+pub fn run(
+  inputs: &[(&'static dyn DynSolution, Source)],
+  parts: &[Part],
+  options: &Options,
+) -> Vec<Entry> {
+  let loaded = pool::map(inputs, options.jobs, |(solution, source)| {
+    load(*solution, source, options)
+  });
+
+  let work = loaded
+    .iter()
+    .flat_map(|loaded| parts.iter().map(move |&part| (loaded, part)))
+    .collect::<Vec<_>>();
+
+  pool::map(&work, options.jobs, |(loaded, part)| {
+    solve(loaded, *part, options)
+  })
+}
+
+// Whether any entry ran on something other than its day's usual input, and so
+// tables need to say which input each row is for.
+pub fn mixed_inputs<'a>(entries: impl IntoIterator<Item = &'a Entry>) -> bool {
+  entries
+    .into_iter()
+    .any(|entry| !fs::is_default_input(entry.day, &entry.input))
+}
+
+pub fn summary(entries: &[Entry]) -> Table {
+  let memory = entries.iter().any(|entry| entry.memory.is_some());
+  let inputs = mixed_inputs(entries);
+
+  let mut columns = vec![
+    ("day", Align::Right),
+    ("part", Align::Right),
+    ("answer", Align::Right),
+    ("parse", Align::Right),
+    ("solve", Align::Right),
+  ];
+  if memory {
+    columns.extend(table::MEMORY_COLUMNS);
+  }
+  columns.push(("status", Align::Left));
+  if inputs {
+    columns.insert(1, ("input", Align::Left));
+  }
+  let mut table = Table::new(&columns);
+
+  for entry in entries {
+    let mut cells = vec![
+      entry.day.to_string(),
+      entry.part.to_string(),
+      entry.answer.clone().unwrap_or_else(|| "-".to_string()),
+      table::duration(entry.parse),
+      table::duration(entry.solve),
+    ];
+    if memory {
+      cells.extend(table::memory(entry.memory));
+    }
+    cells.push(entry.status.to_string());
+    if inputs {
+      cells.insert(1, entry.input.clone());
+    }
+    table.row(cells);
+  }
+
+  // every part of an input shares one parse, so only count it once per input
+  let mut parse_total = Duration::ZERO;
+  let mut last_input = None;
+  for entry in entries {
+    if last_input != Some((entry.day, &entry.input)) {
+      parse_total += entry.parse;
+      last_input = Some((entry.day, &entry.input));
+    }
+  }
+
+  let solve_total = entries.iter().map(|entry| entry.solve).sum::<Duration>();
+  let failed = entries.iter().filter(|entry| !entry.status.is_ok()).count();
+
+  let mut cells = vec![
+    "total".to_string(),
+    entries.len().to_string(),
+    String::new(),
+    table::duration(parse_total),
+    table::duration(solve_total),
+  ];
+  if memory {
+    let total = entries
+      .iter()
+      .filter_map(|entry| entry.memory)
+      .fold(Usage::default(), Usage::combine);
+    cells.extend(table::memory(Some(total)));
+  }
+  cells.push(format!("{} ok, {} failed", entries.len() - failed, failed));
+  if inputs {
+    cells.insert(1, String::new());
+  }
+
+  table.separator();
+  table.row(cells);
+
+  table
+}
+
+pub fn records(entries: &[Entry], alloc: bool) -> Vec<Record> {
+  entries
+    .iter()
+    .map(|entry| {
+      let record = Record::new()
+        .field("day", entry.day)
+        .field("part", entry.part.to_string())
+        .field("input", entry.input.as_str())
+        .field("answer", entry.answer.clone())
+        .field("parse_ns", entry.parse)
+        .field("solve_ns", entry.solve);
+      let record = if alloc {
+        Usage::fields(entry.memory.as_ref(), record)
+      } else {
+        record
+      };
+      record
+        .field("status", entry.status.label())
+        .field("error", entry.status.message())
+    })
+    .collect()
+}
+
+// A finished entry on a day's usual input, for the tests of every module that
+// works with entries.
+#[cfg(test)]
+pub fn entry(day: u8, part: Part, answer: Option<&str>, status: Status) -> Entry {
+  Entry {
+    day,
+    part,
+    input: format!("day_{:02}.txt", day),
+    input_hash: None,
+    answer: answer.map(str::to_string),
+    parse: Duration::ZERO,
+    solve: Duration::ZERO,
+    memory: None,
+    status,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{output, solution::SOLUTIONS};
+
+  #[test]
+  fn test_isolated() {
+    assert!(matches!(isolated(None, || 42), Ok(42)));
+
+    let panicked = isolated(None, || -> u8 { panic!("unexpected cell '{}'", '?') });
+    assert!(
+      matches!(panicked, Err(Failure::Panicked(message)) if message == "unexpected cell '?'")
+    );
+
+    let timeout = Duration::from_millis(10);
+    let timed_out = isolated(Some(timeout), || thread::sleep(Duration::from_secs(1)));
+    assert!(matches!(timed_out, Err(Failure::TimedOut(after)) if after == timeout));
+  }
+
+  #[test]
+  fn test_read_error() {
+    let source = Source::parse("input/missing/day_01.txt");
+    let entries = run(&[(SOLUTIONS[0], source)], &Part::all(), &Options::default());
+
+    assert_eq!(entries.len(), 2);
+    for entry in entries {
+      assert_eq!(entry.status.label(), "read error");
+      assert!(entry
+        .status
+        .to_string()
+        .starts_with("read error: input/missing/day_01.txt: "));
+    }
+  }
+
+  #[test]
+  fn test_records() {
+    let usage = Usage {
+      allocations: 3,
+      bytes: 96,
+      peak: 64,
+    };
+    // the failed entry comes first, so it's the one the header could be taken from
+    let entries = [
+      entry(7, Part::One, None, Status::ParseError("line 1".to_string())),
+      Entry {
+        memory: Some(usage),
+        ..entry(6, Part::One, Some("41"), Status::Ok)
+      },
+    ];
+
+    let expected = "\
+day,part,input,answer,parse_ns,solve_ns,allocations,allocated_bytes,peak_bytes,status,error
+7,1,day_07.txt,,0,0,,,,parse error,line 1
+6,1,day_06.txt,41,0,0,3,96,64,ok,
+";
+    assert_eq!(output::csv(&records(&entries, true)), expected);
+    assert!(!output::csv(&records(&entries, false)).contains("allocations"));
+  }
+}
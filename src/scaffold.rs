@@ -0,0 +1,251 @@
+use std::{
+  fs::{self, OpenOptions},
+  io::{self, ErrorKind, Write},
+  path::{Path, PathBuf},
+};
+
+// A day that parses its input into lines and has no answers yet. Its
+// `test_example` fails until part 1 is solved and the example's answer is
+// filled in, `aoc examples` checks the example once `EXAMPLES` has it too.
+pub fn template(day: u8) -> String {
+  format!(
+    r#"use std::io;
+
+use crate::solution::{{Example, Solution}};
+
+fn parse(input: &str) -> io::Result<Vec<String>> {{
+  Ok(input.lines().map(str::to_string).collect())
+}}
+
+fn part_1(_input: &[String]) -> io::Result<u64> {{
+  Err(io::Error::other("part 1 is not solved yet"))
+}}
+
+fn part_2(_input: &[String]) -> io::Result<u64> {{
+  Err(io::Error::other("part 2 is not solved yet"))
+}}
+
+const EXAMPLE: &str = "\
+";
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+  const DAY: u8 = {day};
+  const EXAMPLES: &'static [Example] = &[Example {{
+    input: EXAMPLE,
+    part_1: None,
+    part_2: None,
+  }}];
+
+  type Input = Vec<String>;
+  type Output = u64;
+
+  fn parse(input: &str) -> io::Result<Self::Input> {{
+    parse(input)
+  }}
+
+  fn part_1(input: &Self::Input) -> io::Result<u64> {{
+    part_1(input)
+  }}
+
+  fn part_2(input: &Self::Input) -> io::Result<u64> {{
+    part_2(input)
+  }}
+}}
+
+#[cfg(test)]
+mod tests {{
+  use super::*;
+
+  #[test]
+  fn test_example() {{
+    let input = parse(EXAMPLE).unwrap();
+    assert_eq!(part_1(&input).unwrap(), 0);
+  }}
+}}
+"#,
+    day = day
+  )
+}
+
+// Days that `solution.rs` already has in its list.
+fn registered(solution: &str) -> Vec<u8> {
+  (1..=25)
+    .filter(|day| solution.contains(&format!("day_{:02}::Day{:02}", day, day)))
+    .collect()
+}
+
+// Adds `entry(day)` next to the entry of the closest registered day, so the
+// list stays in order. `None` when no registered day's entry can be found.
+fn insert_entry(
+  source: &str,
+  days: &[u8],
+  day: u8,
+  separator: &str,
+  entry: impl Fn(u8) -> String,
+) -> Option<String> {
+  let (anchor, after) = match days.iter().filter(|&&other| other < day).max() {
+    Some(&previous) => (entry(previous), true),
+    None => (entry(*days.iter().min()?), false),
+  };
+
+  let index = source.find(&anchor)?;
+  let mut source = source.to_string();
+  if after {
+    source.insert_str(
+      index + anchor.len(),
+      &format!("{}{}", separator, entry(day)),
+    );
+  } else {
+    source.insert_str(index, &format!("{}{}", entry(day), separator));
+  }
+
+  Some(source)
+}
+
+fn unregistrable(path: &Path) -> io::Error {
+  io::Error::new(
+    ErrorKind::InvalidData,
+    format!("could not find where to add the day in {}", path.display()),
+  )
+}
+
+// Creates `src/day_NN.rs` and registers it in `main.rs` and `solution.rs`,
+// returning the files that were written. Nothing is written if the day exists.
+pub fn new_day(src: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+  let module = src.join(format!("day_{:02}.rs", day));
+  let main = src.join("main.rs");
+  let solution = src.join("solution.rs");
+
+  let solution_source = fs::read_to_string(&solution)?;
+  let days = registered(&solution_source);
+  if days.contains(&day) || module.exists() {
+    return Err(io::Error::new(
+      ErrorKind::AlreadyExists,
+      format!("day {} already exists", day),
+    ));
+  }
+
+  let solution_source = insert_entry(&solution_source, &days, day, ", ", |day| {
+    format!("day_{:02}::Day{:02}", day, day)
+  })
+  .and_then(|source| insert_entry(&source, &days, day, ", ", |day| format!("&Day{:02}", day)))
+  .ok_or_else(|| unregistrable(&solution))?;
+
+  let main_source = insert_entry(&fs::read_to_string(&main)?, &days, day, "\n", |day| {
+    format!("mod day_{:02};", day)
+  })
+  .ok_or_else(|| unregistrable(&main))?;
+
+  OpenOptions::new()
+    .write(true)
+    .create_new(true)
+    .open(&module)?
+    .write_all(template(day).as_bytes())?;
+  fs::write(&main, main_source)?;
+  fs::write(&solution, solution_source)?;
+
+  Ok(vec![module, main, solution])
+}
+
+// An empty input for the day, left alone if there already is one. `fetch`
+// treats an empty file as missing, so it still downloads over it.
+pub fn input_placeholder(path: &Path) -> io::Result<bool> {
+  if path.exists() {
+    return Ok(false);
+  }
+
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  fs::write(path, "")?;
+
+  Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::env;
+
+  const MAIN: &str = "\
+mod cli;
+mod day_03;
+mod day_05;
+mod fs;
+";
+
+  const SOLUTION: &str = "\
+use crate::{cli::Part, day_03::Day03, day_05::Day05};
+
+pub static SOLUTIONS: &[&dyn DynSolution] = &[&Day03, &Day05];
+";
+
+  fn src(name: &str) -> PathBuf {
+    let src = env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&src);
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("main.rs"), MAIN).unwrap();
+    fs::write(src.join("solution.rs"), SOLUTION).unwrap();
+    src
+  }
+
+  #[test]
+  fn test_new_day() {
+    let src = src("new");
+
+    new_day(&src, 4).unwrap();
+    new_day(&src, 1).unwrap();
+    new_day(&src, 12).unwrap();
+
+    assert_eq!(
+      fs::read_to_string(src.join("main.rs")).unwrap(),
+      "mod cli;\nmod day_01;\nmod day_03;\nmod day_04;\nmod day_05;\nmod day_12;\nmod fs;\n"
+    );
+    assert_eq!(
+      fs::read_to_string(src.join("solution.rs")).unwrap(),
+      "use crate::{cli::Part, day_01::Day01, day_03::Day03, day_04::Day04, day_05::Day05, \
+       day_12::Day12};\n\n\
+       pub static SOLUTIONS: &[&dyn DynSolution] = &[&Day01, &Day03, &Day04, &Day05, &Day12];\n"
+    );
+    assert_eq!(
+      fs::read_to_string(src.join("day_04.rs")).unwrap(),
+      template(4)
+    );
+    assert!(
+      template(4).contains("pub struct Day04;\n\nimpl Solution for Day04 {\n  const DAY: u8 = 4;")
+    );
+    assert!(template(4).contains(
+      "#[cfg(test)]\nmod tests {\n  use super::*;\n\n  #[test]\n  fn test_example() {\n    \
+       let input = parse(EXAMPLE).unwrap();\n    assert_eq!(part_1(&input).unwrap(), 0);\n  }\n}\n"
+    ));
+
+    fs::remove_dir_all(&src).unwrap();
+  }
+
+  #[test]
+  fn test_existing_day() {
+    let src = src("existing");
+
+    let error = new_day(&src, 5).unwrap_err();
+    assert_eq!(error.to_string(), "day 5 already exists");
+
+    fs::write(src.join("day_07.rs"), "// work in progress").unwrap();
+    assert_eq!(
+      new_day(&src, 7).unwrap_err().kind(),
+      ErrorKind::AlreadyExists
+    );
+    assert_eq!(
+      fs::read_to_string(src.join("day_07.rs")).unwrap(),
+      "// work in progress"
+    );
+    assert_eq!(fs::read_to_string(src.join("main.rs")).unwrap(), MAIN);
+    assert_eq!(
+      fs::read_to_string(src.join("solution.rs")).unwrap(),
+      SOLUTION
+    );
+
+    fs::remove_dir_all(&src).unwrap();
+  }
+}
@@ -0,0 +1,273 @@
+use std::{
+  env, fs,
+  io::{self, ErrorKind},
+  path::Path,
+};
+
+use crate::{
+  cli::Part,
+  http::{self, Response, Url},
+};
+
+pub const YEAR: u16 = 2024;
+// The site itself only serves https, so this needs a local http proxy in front of it.
+pub const DEFAULT_URL: &str = "http://adventofcode.com";
+pub const SESSION_PATH: &str = ".aoc/session";
+const USER_AGENT: &str = "advent-of-code-24 runner (std::net)";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Options {
+  // overrides `AOC_URL`, which overrides `DEFAULT_URL`
+  pub url: Option<String>,
+}
+
+pub struct Client {
+  url: Url,
+  session: String,
+}
+
+// `AOC_SESSION`, or the first line of `.aoc/session`.
+fn session() -> Result<String, String> {
+  if let Ok(session) = env::var("AOC_SESSION") {
+    return Ok(session.trim().to_string());
+  }
+
+  match fs::read_to_string(SESSION_PATH) {
+    Ok(contents) => match contents.lines().next().map(str::trim) {
+      Some(session) if !session.is_empty() => Ok(session.to_string()),
+      _ => Err(format!("{} is empty", SESSION_PATH)),
+    },
+    Err(error) if error.kind() == ErrorKind::NotFound => Err(format!(
+      "no session cookie, set AOC_SESSION or save it in {}",
+      SESSION_PATH
+    )),
+    Err(error) => Err(format!("could not read {}: {}", SESSION_PATH, error)),
+  }
+}
+
+impl Client {
+  pub fn new(url: &str, session: String) -> Result<Client, String> {
+    Ok(Client {
+      url: Url::parse(url)?,
+      session,
+    })
+  }
+
+  pub fn from_env(options: &Options) -> Result<Client, String> {
+    let url = options
+      .url
+      .clone()
+      .or_else(|| env::var("AOC_URL").ok())
+      .unwrap_or_else(|| DEFAULT_URL.to_string());
+
+    Client::new(&url, session()?)
+  }
+
+  fn request(&self, method: &str, path: &str, body: Option<&[u8]>) -> io::Result<Response> {
+    let cookie = format!("session={}", self.session);
+    let mut headers = vec![("Cookie", cookie.as_str()), ("User-Agent", USER_AGENT)];
+    if body.is_some() {
+      headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+    }
+
+    let response = http::request(method, &self.url, path, &headers, body)?;
+
+    match response.status {
+      200 => Ok(response),
+      300..=399 => Err(io::Error::other(format!(
+        "{} redirected to {}, only plain http is supported so point the url at an http proxy",
+        path,
+        response.header("Location").unwrap_or("somewhere else")
+      ))),
+      status => Err(io::Error::other(format!(
+        "{} answered {} {}: {}",
+        path,
+        status,
+        response.reason,
+        response.text().lines().next().unwrap_or_default().trim()
+      ))),
+    }
+  }
+
+  // The input exactly as sent, reading it checks that it is text.
+  pub fn input(&self, day: u8) -> io::Result<Vec<u8>> {
+    let response = self.request("GET", &format!("/{}/day/{}/input", YEAR, day), None)?;
+    Ok(response.body)
+  }
+
+  // Posts an answer, handing back the page the site answers with.
+  pub fn submit(&self, day: u8, part: Part, answer: &str) -> io::Result<String> {
+    let body = format!("level={}&answer={}", part, form_encode(answer));
+    let response = self.request(
+      "POST",
+      &format!("/{}/day/{}/answer", YEAR, day),
+      Some(body.as_bytes()),
+    )?;
+    Ok(response.text())
+  }
+}
+
+fn form_encode(s: &str) -> String {
+  s.bytes()
+    .map(|byte| match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+        (byte as char).to_string()
+      }
+      b' ' => "+".to_string(),
+      _ => format!("%{:02X}", byte),
+    })
+    .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+  Downloaded { bytes: usize },
+  // the file was already there, inputs never change so it isn't downloaded again
+  Cached,
+}
+
+pub fn fetch(client: &Client, day: u8, path: &Path) -> io::Result<Fetched> {
+  // an empty file is the placeholder `aoc new` leaves, not a real input
+  if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+    return Ok(Fetched::Cached);
+  }
+
+  let input = client.input(day)?;
+
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+
+  // write next to the target and move it into place, so an interrupted
+  // download never leaves a partial input behind
+  let partial = path.with_extension("partial");
+  fs::write(&partial, &input)?;
+  fs::rename(&partial, path)?;
+
+  Ok(Fetched::Downloaded { bytes: input.len() })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+  };
+
+  // Answers a single request with `response`, handing back the request it got.
+  fn stand_in(response: &'static [u8]) -> (String, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let server = thread::spawn(move || {
+      let (stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream);
+      let mut head = String::new();
+      let mut length = 0;
+      loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line == "\r\n" || line.is_empty() {
+          break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length: ") {
+          length = value.trim().parse().unwrap();
+        }
+        head.push_str(&line);
+      }
+      // read the body before answering, closing with unread data resets the connection
+      let mut body = vec![0; length];
+      reader.read_exact(&mut body).unwrap();
+      reader.get_mut().write_all(response).unwrap();
+      head + "\r\n" + &String::from_utf8(body).unwrap()
+    });
+
+    (url, server)
+  }
+
+  #[test]
+  fn test_fetch() {
+    let (url, server) = stand_in(b"HTTP/1.1 200 OK\r\nContent-Length: 12\r\n\r\n3   4\n4   3\n");
+    let client = Client::new(&url, "abc123".to_string()).unwrap();
+
+    let dir = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+    let path = dir.join("day_01.txt");
+    let _ = fs::remove_dir_all(&dir);
+    // the placeholder `aoc new` leaves doesn't count as having the input
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(&path, "").unwrap();
+
+    assert_eq!(
+      fetch(&client, 1, &path).unwrap(),
+      Fetched::Downloaded { bytes: 12 }
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "3   4\n4   3\n");
+
+    let head = server.join().unwrap();
+    assert!(head.starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+    assert!(head.contains("Cookie: session=abc123\r\n"));
+    // the stand-in listens on its own port, which the Host header has to carry
+    assert!(head.contains(&format!("Host: {}\r\n", url.trim_start_matches("http://"))));
+
+    // the stand-in is gone, so this must not touch the network
+    assert_eq!(fetch(&client, 1, &path).unwrap(), Fetched::Cached);
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_fetch_bytes() {
+    let (url, server) = stand_in(b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\n3\xff4\n");
+    let client = Client::new(&url, "abc123".to_string()).unwrap();
+
+    let path = env::temp_dir().join(format!("aoc-fetch-bytes-{}.txt", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    // saved as sent, so reading the input is what reports the bad byte
+    fetch(&client, 1, &path).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"3\xff4\n");
+    fs::remove_file(&path).unwrap();
+    server.join().unwrap();
+  }
+
+  #[test]
+  fn test_fetch_error() {
+    let (url, server) = stand_in(
+      b"HTTP/1.1 404 Not Found\r\nContent-Length: 32\r\n\r\nPlease don't repeatedly request\n",
+    );
+    let client = Client::new(&url, "abc123".to_string()).unwrap();
+
+    let path = env::temp_dir().join(format!("aoc-fetch-error-{}.txt", std::process::id()));
+    let error = fetch(&client, 25, &path).unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "/2024/day/25/input answered 404 Not Found: Please don't repeatedly request"
+    );
+    assert!(!path.exists());
+    server.join().unwrap();
+  }
+
+  #[test]
+  fn test_submit() {
+    let (url, server) = stand_in(
+      b"HTTP/1.1 200 OK\r\nContent-Length: 43\r\n\r\n<article>That's the right answer!</article>",
+    );
+    let client = Client::new(&url, "abc123".to_string()).unwrap();
+
+    let page = client.submit(7, Part::Two, "11387").unwrap();
+    assert_eq!(page, "<article>That's the right answer!</article>");
+
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /2024/day/7/answer HTTP/1.1\r\n"));
+    assert!(request.contains("Content-Type: application/x-www-form-urlencoded\r\n"));
+    assert!(request.ends_with("\r\n\r\nlevel=2&answer=11387"));
+  }
+
+  #[test]
+  fn test_form_encode() {
+    assert_eq!(form_encode("11387"), "11387");
+    assert_eq!(form_encode("a b&c=d"), "a+b%26c%3Dd");
+  }
+}
@@ -0,0 +1,90 @@
+use std::{any::Any, fmt::Display, io};
+
+use crate::{
+  cli::Part, day_01::Day01, day_02::Day02, day_03::Day03, day_04::Day04, day_05::Day05,
+  day_06::Day06, day_07::Day07, day_08::Day08, day_09::Day09, day_10::Day10, day_11::Day11,
+  day_12::Day12, day_13::Day13,
+};
+
+// A puzzle's worked example with its known answers, `None` for a part without one.
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+  pub input: &'static str,
+  pub part_1: Option<&'static str>,
+  pub part_2: Option<&'static str>,
+}
+
+impl Example {
+  pub fn answer(&self, part: Part) -> Option<&'static str> {
+    match part {
+      Part::One => self.part_1,
+      Part::Two => self.part_2,
+    }
+  }
+}
+
+pub trait Solution {
+  const DAY: u8;
+  const EXAMPLES: &'static [Example];
+
+  type Input: Send + Sync + 'static;
+  type Output: Display;
+
+  fn parse(input: &str) -> io::Result<Self::Input>;
+  fn part_1(input: &Self::Input) -> io::Result<Self::Output>;
+  fn part_2(input: &Self::Input) -> io::Result<Self::Output>;
+}
+
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+// Object safe view of a `Solution`, so days with different input and output
+// types can live side by side in `SOLUTIONS`.
+pub trait DynSolution: Sync {
+  fn day(&self) -> u8;
+  fn examples(&self) -> &'static [Example];
+  fn parse(&self, input: &str) -> io::Result<Parsed>;
+  fn solve(&self, part: Part, input: &Parsed) -> io::Result<String>;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+  fn day(&self) -> u8 {
+    S::DAY
+  }
+
+  fn examples(&self) -> &'static [Example] {
+    S::EXAMPLES
+  }
+
+  fn parse(&self, input: &str) -> io::Result<Parsed> {
+    Ok(Box::new(S::parse(input)?))
+  }
+
+  fn solve(&self, part: Part, input: &Parsed) -> io::Result<String> {
+    let input = input
+      .downcast_ref::<S::Input>()
+      .expect("input was parsed by a different day");
+
+    let output = match part {
+      Part::One => S::part_1(input)?,
+      Part::Two => S::part_2(input)?,
+    };
+
+    Ok(output.to_string())
+  }
+}
+
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+  &Day01, &Day02, &Day03, &Day04, &Day05, &Day06, &Day07, &Day08, &Day09, &Day10, &Day11, &Day12,
+  &Day13,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+  SOLUTIONS
+    .iter()
+    .find(|solution| solution.day() == day)
+    .copied()
+}
+
+pub fn days() -> Vec<u8> {
+  SOLUTIONS.iter().map(|solution| solution.day()).collect()
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+  pub samples: usize,
+  pub min: Duration,
+  pub median: Duration,
+  pub mean: Duration,
+  pub p95: Duration,
+  pub stddev: Duration,
+  // samples outside the Tukey fences, i.e. more than 1.5 IQR beyond the quartiles
+  pub outliers: usize,
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+  // linear interpolation between the closest ranks
+  let rank = p * (sorted.len() - 1) as f64;
+  let low = rank.floor() as usize;
+  let high = rank.ceil() as usize;
+  sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+}
+
+impl Stats {
+  pub fn new(samples: &[Duration]) -> Option<Stats> {
+    if samples.is_empty() {
+      return None;
+    }
+
+    let mut nanos = samples
+      .iter()
+      .map(|sample| sample.as_nanos() as f64)
+      .collect::<Vec<_>>();
+    nanos.sort_by(|a, b| a.total_cmp(b));
+
+    let count = nanos.len() as f64;
+    let mean = nanos.iter().sum::<f64>() / count;
+    let variance = if nanos.len() > 1 {
+      nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1.0)
+    } else {
+      0.0
+    };
+
+    let q1 = percentile(&nanos, 0.25);
+    let q3 = percentile(&nanos, 0.75);
+    let iqr = q3 - q1;
+    let (low_fence, high_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+    let outliers = nanos
+      .iter()
+      .filter(|&&x| x < low_fence || x > high_fence)
+      .count();
+
+    let duration = |nanos: f64| Duration::from_nanos(nanos.round() as u64);
+
+    Some(Stats {
+      samples: nanos.len(),
+      min: duration(nanos[0]),
+      median: duration(percentile(&nanos, 0.5)),
+      mean: duration(mean),
+      p95: duration(percentile(&nanos, 0.95)),
+      stddev: duration(variance.sqrt()),
+      outliers,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn millis(values: &[u64]) -> Vec<Duration> {
+    values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+  }
+
+  #[test]
+  fn test_empty() {
+    assert_eq!(Stats::new(&[]), None);
+  }
+
+  #[test]
+  fn test_stats() {
+    let stats = Stats::new(&millis(&[5, 1, 4, 2, 3])).unwrap();
+    assert_eq!(stats.samples, 5);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_millis(3));
+    assert_eq!(stats.mean, Duration::from_millis(3));
+    assert_eq!(stats.p95, Duration::from_micros(4800));
+    // sample standard deviation of 1..=5 is sqrt(2.5)
+    assert_eq!(stats.stddev, Duration::from_nanos(1_581_139));
+    assert_eq!(stats.outliers, 0);
+  }
+
+  #[test]
+  fn test_outliers() {
+    let stats = Stats::new(&millis(&[10, 11, 10, 12, 11, 10, 90])).unwrap();
+    assert_eq!(stats.median, Duration::from_millis(11));
+    assert_eq!(stats.outliers, 1);
+  }
+}
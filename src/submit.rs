@@ -0,0 +1,477 @@
+use std::{
+  fmt::{self, Display},
+  fs::{self, OpenOptions},
+  io::{self, ErrorKind, Write},
+  path::Path,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::cli::Part;
+
+pub const PATH: &str = ".aoc/submissions.csv";
+const HEADER: &str = "time,day,part,verdict,wait_secs,answer";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+  Right,
+  Wrong,
+  TooHigh,
+  TooLow,
+  // submitted during a cooldown, the answer wasn't checked
+  TooSoon,
+  // the part was already solved, the answer wasn't checked
+  AlreadySolved,
+  Unrecognised,
+}
+
+impl Verdict {
+  pub fn label(&self) -> &'static str {
+    match self {
+      Verdict::Right => "right",
+      Verdict::Wrong => "wrong",
+      Verdict::TooHigh => "too-high",
+      Verdict::TooLow => "too-low",
+      Verdict::TooSoon => "too-soon",
+      Verdict::AlreadySolved => "already-solved",
+      Verdict::Unrecognised => "unrecognised",
+    }
+  }
+
+  fn from_label(s: &str) -> Option<Verdict> {
+    [
+      Verdict::Right,
+      Verdict::Wrong,
+      Verdict::TooHigh,
+      Verdict::TooLow,
+      Verdict::TooSoon,
+      Verdict::AlreadySolved,
+      Verdict::Unrecognised,
+    ]
+    .into_iter()
+    .find(|verdict| verdict.label() == s)
+  }
+
+  pub fn is_wrong(&self) -> bool {
+    matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+  }
+}
+
+impl Display for Verdict {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Verdict::Right => write!(f, "right answer"),
+      Verdict::Wrong => write!(f, "wrong answer"),
+      Verdict::TooHigh => write!(f, "wrong answer, too high"),
+      Verdict::TooLow => write!(f, "wrong answer, too low"),
+      Verdict::TooSoon => write!(f, "submitted too soon"),
+      Verdict::AlreadySolved => write!(f, "already solved"),
+      Verdict::Unrecognised => write!(f, "unrecognised response"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+  pub verdict: Verdict,
+  // how long the site wants us to wait before the next submission
+  pub wait: Option<Duration>,
+  pub message: String,
+}
+
+// Keeps the text of the page's `<article>`, which is where the site puts its verdict.
+fn article_text(html: &str) -> String {
+  let article = match (html.find("<article"), html.find("</article>")) {
+    (Some(start), Some(end)) if start < end => &html[start..end],
+    _ => html,
+  };
+
+  let mut text = String::new();
+  let mut in_tag = false;
+  for c in article.chars() {
+    match c {
+      '<' => in_tag = true,
+      '>' => in_tag = false,
+      c if !in_tag => text.push(c),
+      _ => {}
+    }
+  }
+
+  text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn number(word: &str) -> Option<u64> {
+  match word {
+    "a" | "an" | "one" => Some(1),
+    "two" => Some(2),
+    "three" => Some(3),
+    "four" => Some(4),
+    "five" => Some(5),
+    _ => word.parse().ok(),
+  }
+}
+
+// "please wait one minute before trying again" or "you have 4m 30s left to wait"
+fn wait(message: &str) -> Option<Duration> {
+  let lower = message.to_lowercase();
+  let words = lower
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|word| !word.is_empty())
+    .collect::<Vec<_>>();
+
+  if let Some(index) = words.windows(3).position(|w| w == ["left", "to", "wait"]) {
+    let secs = words[..index]
+      .iter()
+      .rev()
+      .map_while(|word| {
+        let (value, unit) = word.split_at(word.find(|c: char| !c.is_ascii_digit())?);
+        let value = value.parse::<u64>().ok()?;
+        match unit {
+          "h" => Some(value * 3600),
+          "m" => Some(value * 60),
+          "s" => Some(value),
+          _ => None,
+        }
+      })
+      .sum::<u64>();
+    return Some(Duration::from_secs(secs));
+  }
+
+  let index = words.iter().position(|&word| word == "wait")?;
+  let value = number(words.get(index + 1)?)?;
+  match *words.get(index + 2)? {
+    "second" | "seconds" => Some(Duration::from_secs(value)),
+    "minute" | "minutes" => Some(Duration::from_secs(value * 60)),
+    "hour" | "hours" => Some(Duration::from_secs(value * 3600)),
+    _ => None,
+  }
+}
+
+pub fn parse_response(html: &str) -> Response {
+  let message = article_text(html);
+  let lower = message.to_lowercase();
+
+  let verdict = if lower.contains("that's the right answer") {
+    Verdict::Right
+  } else if lower.contains("answer too recently") {
+    Verdict::TooSoon
+  } else if lower.contains("solving the right level") {
+    Verdict::AlreadySolved
+  } else if lower.contains("too high") {
+    Verdict::TooHigh
+  } else if lower.contains("too low") {
+    Verdict::TooLow
+  } else if lower.contains("not the right answer") {
+    Verdict::Wrong
+  } else {
+    Verdict::Unrecognised
+  };
+
+  Response {
+    verdict,
+    wait: wait(&message),
+    message,
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+  // seconds since the unix epoch
+  pub time: u64,
+  pub day: u8,
+  pub part: Part,
+  pub answer: String,
+  pub verdict: Verdict,
+  pub wait: Option<Duration>,
+}
+
+pub fn now() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |now| now.as_secs())
+}
+
+fn parse_submission(line: &str) -> Option<Submission> {
+  // the answer goes last so it may contain commas
+  let mut fields = line.splitn(6, ',');
+  let time = fields.next()?.parse().ok()?;
+  let day = fields.next()?.parse().ok()?;
+  let part = fields.next()?.parse().ok()?;
+  let verdict = Verdict::from_label(fields.next()?)?;
+  let wait = match fields.next()? {
+    "" => None,
+    secs => Some(Duration::from_secs(secs.parse().ok()?)),
+  };
+  let answer = fields.next()?.to_string();
+
+  Some(Submission {
+    time,
+    day,
+    part,
+    answer,
+    verdict,
+    wait,
+  })
+}
+
+// A missing log means nothing was submitted yet.
+pub fn load(path: &Path) -> io::Result<Vec<Submission>> {
+  let contents = match fs::read_to_string(path) {
+    Ok(contents) => contents,
+    Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+    Err(error) => return Err(error),
+  };
+
+  contents
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.is_empty() && *line != HEADER)
+    .map(|(index, line)| {
+      parse_submission(line).ok_or_else(|| {
+        io::Error::new(
+          ErrorKind::InvalidData,
+          format!(
+            "{}:{}: malformed submission '{}'",
+            path.display(),
+            index + 1,
+            line
+          ),
+        )
+      })
+    })
+    .collect()
+}
+
+pub fn append(path: &Path, submission: &Submission) -> io::Result<()> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+
+  let is_new = !path.exists();
+  let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+  if is_new {
+    writeln!(file, "{}", HEADER)?;
+  }
+
+  writeln!(
+    file,
+    "{},{},{},{},{},{}",
+    submission.time,
+    submission.day,
+    submission.part,
+    submission.verdict.label(),
+    submission
+      .wait
+      .map_or(String::new(), |wait| wait.as_secs().to_string()),
+    submission.answer
+  )
+}
+
+// Why an answer isn't worth sending, judging by what was submitted before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+  Solved { answer: String },
+  KnownWrong { verdict: Verdict },
+  // a number at or past an answer that was too high or too low
+  OutOfBounds { bound: String, verdict: Verdict },
+  Cooldown { left: Duration },
+}
+
+impl Display for Refusal {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Refusal::Solved { answer } => write!(f, "already solved with {}", answer),
+      Refusal::KnownWrong { verdict } => write!(f, "already submitted, {}", verdict),
+      Refusal::OutOfBounds { bound, verdict } => {
+        write!(f, "{} was already {}", bound, verdict)
+      }
+      Refusal::Cooldown { left } => {
+        write!(f, "the site asked to wait, {}s left", left.as_secs())
+      }
+    }
+  }
+}
+
+pub fn check(
+  submissions: &[Submission],
+  day: u8,
+  part: Part,
+  answer: &str,
+  now: u64,
+) -> Result<(), Refusal> {
+  // the site's cooldown holds for every puzzle, not just the one that was wrong
+  let ready_at = submissions
+    .iter()
+    .filter_map(|submission| Some(submission.time + submission.wait?.as_secs()))
+    .max();
+  if let Some(ready_at) = ready_at.filter(|&ready_at| ready_at > now) {
+    return Err(Refusal::Cooldown {
+      left: Duration::from_secs(ready_at - now),
+    });
+  }
+
+  let previous = submissions
+    .iter()
+    .filter(|submission| submission.day == day && submission.part == part);
+
+  for submission in previous {
+    match submission.verdict {
+      Verdict::Right => {
+        return Err(Refusal::Solved {
+          answer: submission.answer.clone(),
+        })
+      }
+      verdict if verdict.is_wrong() && submission.answer == answer => {
+        return Err(Refusal::KnownWrong { verdict })
+      }
+      verdict @ (Verdict::TooHigh | Verdict::TooLow) => {
+        let (Ok(value), Ok(bound)) = (answer.parse::<i64>(), submission.answer.parse::<i64>())
+        else {
+          continue;
+        };
+        if (verdict == Verdict::TooHigh && value >= bound)
+          || (verdict == Verdict::TooLow && value <= bound)
+        {
+          return Err(Refusal::OutOfBounds {
+            bound: submission.answer.clone(),
+            verdict,
+          });
+        }
+      }
+      _ => {}
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn page(article: &str) -> String {
+    format!(
+      "<html><body><main><article><p>{}</p></article></main></body></html>",
+      article
+    )
+  }
+
+  #[test]
+  fn test_parse_response() {
+    let right = parse_response(&page(
+      "That's the right answer! You are <span class=\"day-success\">one gold star</span> closer.",
+    ));
+    assert_eq!((right.verdict, right.wait), (Verdict::Right, None));
+
+    let high = parse_response(&page(
+      "That's not the right answer; your answer is too high. If you're stuck, make sure you're \
+       using the full input data. Please wait one minute before trying again. \
+       <a href=\"/2024/day/7\">[Return to Day 7]</a>",
+    ));
+    assert_eq!(
+      (high.verdict, high.wait),
+      (Verdict::TooHigh, Some(Duration::from_secs(60)))
+    );
+
+    let wrong = parse_response(&page(
+      "That's not the right answer. Please wait 5 minutes before trying again.",
+    ));
+    assert_eq!(
+      (wrong.verdict, wrong.wait),
+      (Verdict::Wrong, Some(Duration::from_secs(300)))
+    );
+
+    let soon = parse_response(&page(
+      "You gave an answer too recently; you have to wait after submitting an answer before \
+       trying again. You have 4m 30s left to wait.",
+    ));
+    assert_eq!(
+      (soon.verdict, soon.wait),
+      (Verdict::TooSoon, Some(Duration::from_secs(270)))
+    );
+
+    let solved = parse_response(&page(
+      "You don't seem to be solving the right level. Did you already complete it?",
+    ));
+    assert_eq!(solved.verdict, Verdict::AlreadySolved);
+
+    assert_eq!(
+      parse_response("<html>Puzzle inputs differ by user.</html>").verdict,
+      Verdict::Unrecognised
+    );
+  }
+
+  fn submission(time: u64, part: Part, answer: &str, verdict: Verdict, wait: u64) -> Submission {
+    Submission {
+      time,
+      day: 7,
+      part,
+      answer: answer.to_string(),
+      verdict,
+      wait: Some(Duration::from_secs(wait)).filter(|wait| !wait.is_zero()),
+    }
+  }
+
+  #[test]
+  fn test_check() {
+    let submissions = vec![
+      submission(1000, Part::One, "3749", Verdict::Right, 0),
+      submission(2000, Part::Two, "100", Verdict::TooLow, 60),
+      submission(2100, Part::Two, "900", Verdict::TooHigh, 60),
+      submission(2200, Part::Two, "abc", Verdict::Wrong, 60),
+    ];
+
+    assert_eq!(
+      check(&submissions, 7, Part::Two, "500", 2200),
+      Err(Refusal::Cooldown {
+        left: Duration::from_secs(60)
+      })
+    );
+    assert_eq!(check(&submissions, 7, Part::Two, "500", 2260), Ok(()));
+    assert_eq!(check(&submissions, 8, Part::One, "500", 2260), Ok(()));
+    assert_eq!(
+      check(&submissions, 7, Part::One, "4000", 2260),
+      Err(Refusal::Solved {
+        answer: "3749".to_string()
+      })
+    );
+    assert_eq!(
+      check(&submissions, 7, Part::Two, "abc", 2260),
+      Err(Refusal::KnownWrong {
+        verdict: Verdict::Wrong
+      })
+    );
+    assert_eq!(
+      check(&submissions, 7, Part::Two, "950", 2260),
+      Err(Refusal::OutOfBounds {
+        bound: "900".to_string(),
+        verdict: Verdict::TooHigh
+      })
+    );
+    assert_eq!(
+      check(&submissions, 7, Part::Two, "100", 2260),
+      Err(Refusal::KnownWrong {
+        verdict: Verdict::TooLow
+      })
+    );
+  }
+
+  #[test]
+  fn test_log() {
+    let path = std::env::temp_dir().join(format!("aoc-submissions-{}.csv", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    assert_eq!(load(&path).unwrap(), Vec::new());
+
+    let submissions = vec![
+      submission(1000, Part::One, "3749", Verdict::Right, 0),
+      submission(2000, Part::Two, "1,2", Verdict::TooSoon, 270),
+    ];
+    for submission in &submissions {
+      append(&path, submission).unwrap();
+    }
+    assert_eq!(load(&path).unwrap(), submissions);
+
+    fs::remove_file(&path).unwrap();
+  }
+}
@@ -0,0 +1,148 @@
+use std::{
+  fmt::{self, Display},
+  time::Duration,
+};
+
+use crate::alloc::Usage;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Align {
+  Left,
+  Right,
+}
+
+pub struct Table {
+  columns: Vec<(String, Align)>,
+  rows: Vec<Row>,
+}
+
+enum Row {
+  Cells(Vec<String>),
+  Separator,
+}
+
+impl Table {
+  pub fn new(columns: &[(&str, Align)]) -> Self {
+    Self {
+      columns: columns
+        .iter()
+        .map(|(header, align)| (header.to_string(), *align))
+        .collect(),
+      rows: Vec::new(),
+    }
+  }
+
+  pub fn row(&mut self, cells: Vec<String>) {
+    self.rows.push(Row::Cells(cells));
+  }
+
+  pub fn separator(&mut self) {
+    self.rows.push(Row::Separator);
+  }
+
+  fn widths(&self) -> Vec<usize> {
+    let mut widths = self
+      .columns
+      .iter()
+      .map(|(header, _)| header.chars().count())
+      .collect::<Vec<_>>();
+
+    for row in self.rows.iter() {
+      if let Row::Cells(cells) = row {
+        for (width, cell) in widths.iter_mut().zip(cells.iter()) {
+          *width = (*width).max(cell.chars().count());
+        }
+      }
+    }
+
+    widths
+  }
+}
+
+impl Display for Table {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let widths = self.widths();
+
+    let write_cells = |f: &mut fmt::Formatter<'_>, cells: &[String]| -> fmt::Result {
+      let line = self
+        .columns
+        .iter()
+        .zip(widths.iter())
+        .zip(cells.iter())
+        .map(|(((_, align), width), cell)| match align {
+          Align::Left => format!("{:<width$}", cell, width = width),
+          Align::Right => format!("{:>width$}", cell, width = width),
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+      writeln!(f, "{}", line.trim_end())
+    };
+
+    let separator = widths
+      .iter()
+      .map(|width| "-".repeat(*width))
+      .collect::<Vec<_>>()
+      .join("  ");
+
+    let headers = self
+      .columns
+      .iter()
+      .map(|(header, _)| header.clone())
+      .collect::<Vec<_>>();
+
+    write_cells(f, &headers)?;
+    writeln!(f, "{}", separator)?;
+
+    for row in self.rows.iter() {
+      match row {
+        Row::Cells(cells) => write_cells(f, cells)?,
+        Row::Separator => writeln!(f, "{}", separator)?,
+      }
+    }
+
+    Ok(())
+  }
+}
+
+pub fn duration(duration: Duration) -> String {
+  let nanos = duration.as_nanos();
+  if nanos < 1_000 {
+    format!("{}ns", nanos)
+  } else if nanos < 1_000_000 {
+    format!("{:.1}µs", nanos as f64 / 1e3)
+  } else if nanos < 1_000_000_000 {
+    format!("{:.3}ms", nanos as f64 / 1e6)
+  } else {
+    format!("{:.3}s", nanos as f64 / 1e9)
+  }
+}
+
+pub fn bytes(bytes: u64) -> String {
+  if bytes < 1 << 10 {
+    format!("{}B", bytes)
+  } else if bytes < 1 << 20 {
+    format!("{:.1}KiB", bytes as f64 / (1u64 << 10) as f64)
+  } else if bytes < 1 << 30 {
+    format!("{:.1}MiB", bytes as f64 / (1u64 << 20) as f64)
+  } else {
+    format!("{:.2}GiB", bytes as f64 / (1u64 << 30) as f64)
+  }
+}
+
+pub const MEMORY_COLUMNS: [(&str, Align); 3] = [
+  ("allocs", Align::Right),
+  ("allocated", Align::Right),
+  ("peak", Align::Right),
+];
+
+// Cells for `MEMORY_COLUMNS`, dashes when nothing was measured.
+pub fn memory(usage: Option<Usage>) -> Vec<String> {
+  match usage {
+    Some(usage) => vec![
+      usage.allocations.to_string(),
+      bytes(usage.bytes),
+      bytes(usage.peak),
+    ],
+    None => vec!["-".to_string(); 3],
+  }
+}
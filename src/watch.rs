@@ -0,0 +1,182 @@
+use std::{
+  collections::BTreeMap,
+  fs,
+  path::PathBuf,
+  thread,
+  time::{Duration, SystemTime},
+};
+
+use crate::{
+  cli::Part,
+  fs::Source,
+  runner::{self, Entry},
+  solution::DynSolution,
+  table::{self, Align, Table},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Options {
+  // extra files to run the day against besides its own input, e.g. the puzzle's example
+  pub also: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+  New,
+  Same,
+  Changed { previous: Option<String> },
+}
+
+type Answers = BTreeMap<(String, Part), Option<String>>;
+
+// `None` for a file that can't be read right now, editors often replace files
+// rather than write them in place.
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+  paths
+    .iter()
+    .map(|path| {
+      fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+    })
+    .collect()
+}
+
+pub fn changes(previous: &Answers, entries: &[Entry]) -> Vec<Change> {
+  entries
+    .iter()
+    .map(
+      |entry| match previous.get(&(entry.input.clone(), entry.part)) {
+        None => Change::New,
+        Some(answer) if *answer == entry.answer => Change::Same,
+        Some(answer) => Change::Changed {
+          previous: answer.clone(),
+        },
+      },
+    )
+    .collect()
+}
+
+pub fn report(entries: &[Entry], changes: &[Change]) -> Table {
+  let mut table = Table::new(&[
+    ("input", Align::Left),
+    ("part", Align::Right),
+    ("previous", Align::Right),
+    ("answer", Align::Right),
+    ("solve", Align::Right),
+    ("status", Align::Left),
+  ]);
+
+  for (entry, change) in entries.iter().zip(changes) {
+    let answer = entry.answer.clone().unwrap_or_else(|| "-".to_string());
+    let (previous, note) = match change {
+      Change::New => ("-".to_string(), "new"),
+      Change::Same => (answer.clone(), "same"),
+      Change::Changed { previous } => (
+        previous.clone().unwrap_or_else(|| "-".to_string()),
+        "CHANGED",
+      ),
+    };
+
+    let status = if entry.status.is_ok() {
+      note.to_string()
+    } else {
+      format!("{}, {}", note, entry.status)
+    };
+
+    table.row(vec![
+      entry.input.clone(),
+      entry.part.to_string(),
+      previous,
+      answer,
+      table::duration(entry.solve),
+      status,
+    ]);
+  }
+
+  table
+}
+
+// Polls the inputs and re-runs the day whenever one of them changes, until interrupted.
+pub fn watch(
+  solution: &'static dyn DynSolution,
+  input: PathBuf,
+  parts: &[Part],
+  runner_options: &runner::Options,
+  options: &Options,
+) -> ! {
+  let mut paths = vec![input];
+  paths.extend(options.also.iter().cloned());
+
+  let inputs = paths
+    .iter()
+    .map(|path| (solution, Source::Path(path.clone())))
+    .collect::<Vec<_>>();
+
+  eprintln!(
+    "watching {}, press ctrl-c to stop",
+    paths
+      .iter()
+      .map(|path| path.display().to_string())
+      .collect::<Vec<_>>()
+      .join(", ")
+  );
+
+  let mut previous = Answers::new();
+  let mut last_seen = None;
+
+  loop {
+    let seen = modified(&paths);
+    if last_seen.as_ref() != Some(&seen) {
+      if last_seen.is_some() {
+        println!();
+      }
+      last_seen = Some(seen);
+
+      let entries = runner::run(&inputs, parts, runner_options);
+      let changes = changes(&previous, &entries);
+      println!("day {}", solution.day());
+      print!("{}", report(&entries, &changes));
+
+      previous = entries
+        .into_iter()
+        .map(|entry| ((entry.input, entry.part), entry.answer))
+        .collect();
+    }
+
+    thread::sleep(POLL_INTERVAL);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::runner::{entry, Status};
+
+  #[test]
+  fn test_changes() {
+    let previous = Answers::from([(
+      ("day_07.txt".to_string(), Part::One),
+      Some("3749".to_string()),
+    )]);
+
+    let entries = [
+      entry(7, Part::One, Some("3749"), Status::Ok),
+      entry(7, Part::Two, None, Status::Ok),
+    ];
+    assert_eq!(
+      changes(&previous, &entries),
+      vec![Change::Same, Change::New]
+    );
+
+    let entries = [entry(7, Part::One, Some("11387"), Status::Ok)];
+    assert_eq!(
+      changes(&previous, &entries),
+      vec![Change::Changed {
+        previous: Some("3749".to_string())
+      }]
+    );
+  }
+}